@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use solver::{Part, SolveError, Solver};
+use timing::{self, format_duration, Stats};
+
+/// A stage of a day's solver that is timed on its own.
+///
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Phase {
+    /// Parsing the input only.
+    Parse,
+    /// Solving a part, including parsing the input, which every part does first.
+    Solve(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Solve(part) => f.pad(&format!("part{}", part)),
+        }
+    }
+}
+
+impl Phase {
+    fn from_token(token: &str) -> Option<Phase> {
+        match token {
+            "parse" => Some(Phase::Parse),
+            "part1" => Some(Phase::Solve(Part::One)),
+            "part2" => Some(Phase::Solve(Part::Two)),
+            _ => None,
+        }
+    }
+}
+
+/// The timings of one phase of one day.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Median timings from an earlier run, keyed by day and phase.
+///
+pub type Baseline = HashMap<(u8, Phase), Duration>;
+
+/// Time `runs` runs of each phase of the solver against the input.
+///
+/// A solver only takes its input unparsed, so each run of a part includes parsing it. The table
+/// shows how far a part's median is above the parse median as well.
+///
+pub fn bench_solver(solver: &dyn Solver, input: &str, runs: usize) -> Result<Vec<Measurement>, SolveError> {
+    let mut measurements = Vec::new();
+    let phases = [Phase::Parse, Phase::Solve(Part::One), Phase::Solve(Part::Two)];
+
+    for phase in &phases {
+        let mut samples = Vec::with_capacity(runs);
+        for _ in 0..runs {
+            let (result, elapsed) = timing::time(|| match phase {
+                Phase::Parse => solver.parse(input).map(|_| ()),
+                Phase::Solve(part) => solver.solve(*part, input).map(|_| ()),
+            });
+            result?;
+            samples.push(elapsed);
+        }
+        if let Some(stats) = Stats::from_samples(&samples) {
+            measurements.push(Measurement { day: solver.day(), phase: *phase, stats });
+        }
+    }
+    Ok(measurements)
+}
+
+/// Read a baseline saved by `save_baseline`, which is empty if there is none yet.
+///
+pub fn load_baseline(path: &Path) -> io::Result<Baseline> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Baseline::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(contents.lines().filter_map(|line| {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields.as_slice() {
+            [day, phase, nanos] => Some((
+                (day.parse().ok()?, Phase::from_token(phase)?),
+                Duration::from_nanos(nanos.parse().ok()?),
+            )),
+            _ => None,
+        }
+    }).collect())
+}
+
+/// Save the median of each measurement as the baseline for later runs. Days and phases that were
+/// not measured keep the baseline they already had.
+///
+pub fn save_baseline(path: &Path, measurements: &[Measurement]) -> io::Result<()> {
+    let mut baseline = load_baseline(path)?;
+    baseline.extend(measurements.iter().map(|m| ((m.day, m.phase), m.stats.median)));
+
+    let mut entries: Vec<(&(u8, Phase), &Duration)> = baseline.iter().collect();
+    entries.sort_by_key(|((day, phase), _)| (*day, phase.to_string()));
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents: String = entries.iter()
+        .map(|((day, phase), median)| format!("{}\t{}\t{}\n", day, phase, median.as_nanos()))
+        .collect();
+    fs::write(path, contents)
+}
+
+/// How the median compares with the baseline, e.g. `+25.0% slower`.
+///
+fn change(median: Duration, baseline: Duration) -> String {
+    if baseline.as_nanos() == 0 {
+        return String::new();
+    }
+    let ratio = median.as_nanos() as f64 / baseline.as_nanos() as f64;
+    let percent = (ratio - 1.0) * 100.0;
+    if ratio > 1.1 {
+        format!("{:+.1}% slower", percent)
+    } else if ratio < 0.9 {
+        format!("{:+.1}% faster", percent)
+    } else {
+        format!("{:+.1}%", percent)
+    }
+}
+
+/// How much longer the first duration is than the second, with a `-` if it is shorter.
+///
+fn difference(duration: Duration, less: Duration) -> String {
+    match duration.checked_sub(less) {
+        Some(more) => format_duration(more),
+        None => format!("-{}", format_duration(less - duration)),
+    }
+}
+
+/// A table of the measurements, compared with the baseline where it has them. Parts, which parse
+/// their input first, also show their median less the day's parse median.
+///
+pub fn render_table(measurements: &[Measurement], baseline: &Baseline) -> String {
+    let mut table = format!("{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>14}  {:>10}  {}\n",
+                            "Day", "Phase", "Min", "Median", "Max", "Median - parse", "Baseline", "Change");
+    for m in measurements {
+        let parse_median = measurements.iter()
+            .find(|p| p.day == m.day && p.phase == Phase::Parse)
+            .map(|p| p.stats.median);
+        let less_parse = match (m.phase, parse_median) {
+            (Phase::Solve(_), Some(parse_median)) => difference(m.stats.median, parse_median),
+            _ => String::from("-"),
+        };
+        let (base, change) = match baseline.get(&(m.day, m.phase)) {
+            Some(base) => (format_duration(*base), change(m.stats.median, *base)),
+            None => (String::from("-"), String::new()),
+        };
+        let row = format!("{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>14}  {:>10}  {}",
+                          m.day, m.phase, format_duration(m.stats.min), format_duration(m.stats.median),
+                          format_duration(m.stats.max), less_parse, base, change);
+        table.push_str(row.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod bench_tests {
+    use super::*;
+    use std::thread;
+    use day05::Day05;
+    use solver::Answer;
+    use temp_dir::TempDir;
+
+    #[test]
+    fn measures_each_phase() {
+        let measurements = bench_solver(&Day05, "dabAcCaCBAcCcaDA", 3).unwrap();
+        let phases: Vec<Phase> = measurements.iter().map(|m| m.phase).collect();
+        assert_eq!(phases, vec![Phase::Parse, Phase::Solve(Part::One), Phase::Solve(Part::Two)]);
+        assert!(measurements.iter().all(|m| m.day == 5 && m.stats.min <= m.stats.max));
+    }
+
+    #[test]
+    fn baseline_round_trips_medians() {
+        let dir = TempDir::new("bench");
+        let path = dir.join("baseline.tsv");
+        let stats = Stats { min: Duration::from_micros(1), median: Duration::from_micros(2), max: Duration::from_micros(3) };
+        save_baseline(&path, &[Measurement { day: 4, phase: Phase::Solve(Part::Two), stats }]).unwrap();
+
+        let baseline = load_baseline(&path).unwrap();
+        assert_eq!(baseline.get(&(4, Phase::Solve(Part::Two))), Some(&Duration::from_micros(2)));
+        assert_eq!(baseline.len(), 1);
+    }
+
+    #[test]
+    fn saving_keeps_the_baseline_of_days_not_measured() {
+        let dir = TempDir::new("bench-merge");
+        let path = dir.join("baseline.tsv");
+        let stats = |micros| Stats { min: Duration::from_micros(micros), median: Duration::from_micros(micros), max: Duration::from_micros(micros) };
+        save_baseline(&path, &[Measurement { day: 1, phase: Phase::Parse, stats: stats(10) }]).unwrap();
+        save_baseline(&path, &[Measurement { day: 5, phase: Phase::Parse, stats: stats(20) }]).unwrap();
+        let baseline = load_baseline(&path).unwrap();
+        assert_eq!(baseline.get(&(1, Phase::Parse)), Some(&Duration::from_micros(10)));
+        assert_eq!(baseline.get(&(5, Phase::Parse)), Some(&Duration::from_micros(20)));
+
+        save_baseline(&path, &[Measurement { day: 1, phase: Phase::Parse, stats: stats(30) },
+                               Measurement { day: 1, phase: Phase::Solve(Part::One), stats: stats(40) }]).unwrap();
+        let baseline = load_baseline(&path).unwrap();
+        assert_eq!(baseline.get(&(1, Phase::Parse)), Some(&Duration::from_micros(30)));
+        assert_eq!(baseline.get(&(1, Phase::Solve(Part::One))), Some(&Duration::from_micros(40)));
+        assert_eq!(baseline.get(&(5, Phase::Parse)), Some(&Duration::from_micros(20)));
+        assert_eq!(baseline.len(), 3);
+    }
+
+    struct SlowParse;
+
+    impl Solver for SlowParse {
+        fn day(&self) -> u8 {
+            99
+        }
+
+        fn title(&self) -> &'static str {
+            "Slow Parse"
+        }
+
+        fn parse(&self, _input: &str) -> Result<usize, SolveError> {
+            thread::sleep(Duration::from_millis(20));
+            Ok(1)
+        }
+
+        fn part_one(&self, input: &str) -> Result<Answer, SolveError> {
+            Ok(self.parse(input)?.into())
+        }
+
+        fn part_two(&self, input: &str) -> Result<Answer, SolveError> {
+            self.part_one(input)
+        }
+    }
+
+    #[test]
+    fn solve_phase_includes_parsing() {
+        let measurements = bench_solver(&SlowParse, "", 3).unwrap();
+        assert!(measurements.iter().all(|m| m.stats.min >= Duration::from_millis(20)));
+    }
+
+    #[test]
+    fn table_shows_parts_less_the_parse_median() {
+        let stats = |micros| Stats { min: Duration::from_micros(micros), median: Duration::from_micros(micros), max: Duration::from_micros(micros) };
+        let measurements = vec![
+            Measurement { day: 5, phase: Phase::Parse, stats: stats(10) },
+            Measurement { day: 5, phase: Phase::Solve(Part::One), stats: stats(25) },
+            Measurement { day: 5, phase: Phase::Solve(Part::Two), stats: stats(8) },
+        ];
+        let mut baseline = Baseline::new();
+        baseline.insert((5, Phase::Solve(Part::One)), Duration::from_micros(20));
+        assert_eq!(render_table(&measurements, &baseline), "\
+Day  Phase         Min      Median         Max  Median - parse    Baseline  Change
+  5  parse    10.000µs    10.000µs    10.000µs               -           -
+  5  part1    25.000µs    25.000µs    25.000µs        15.000µs    20.000µs  +25.0% slower
+  5  part2     8.000µs     8.000µs     8.000µs        -2.000µs           -
+");
+    }
+
+    #[test]
+    fn change_reports_regressions_and_speedups() {
+        assert_eq!(change(Duration::from_millis(150), Duration::from_millis(100)), "+50.0% slower");
+        assert_eq!(change(Duration::from_millis(50), Duration::from_millis(100)), "-50.0% faster");
+        assert_eq!(change(Duration::from_millis(102), Duration::from_millis(100)), "+2.0%");
+    }
+}
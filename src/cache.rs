@@ -0,0 +1,114 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Where downloaded inputs are kept, relative to the working directory.
+///
+pub const DEFAULT_CACHE_DIR: &str = "inputs";
+
+/// An on-disk cache of one year's puzzle inputs, laid out as `<root>/2018/day05.txt`.
+///
+pub struct InputCache {
+    root: PathBuf,
+    year: u16,
+}
+
+impl InputCache {
+    pub fn new<P: Into<PathBuf>>(root: P, year: u16) -> InputCache {
+        InputCache { root: root.into(), year }
+    }
+
+    /// The year whose inputs are cached.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn year_dir(&self) -> PathBuf {
+        self.root.join(self.year.to_string())
+    }
+
+    pub fn path_for(&self, day: u8) -> PathBuf {
+        self.year_dir().join(format!("day{:02}.txt", day))
+    }
+
+    /// Where the ledger of submitted answers is kept, alongside the inputs.
+    pub fn ledger_path(&self) -> PathBuf {
+        self.year_dir().join("ledger.tsv")
+    }
+
+    /// Where `bench` keeps its baseline timings.
+    pub fn bench_baseline_path(&self) -> PathBuf {
+        self.year_dir().join("bench-baseline.tsv")
+    }
+
+    /// Where the day's puzzle description is kept, as Markdown.
+    pub fn description_path(&self, day: u8) -> PathBuf {
+        self.year_dir().join(format!("day{:02}.md", day))
+    }
+
+    /// The cached input for the day, if it has been downloaded before.
+    pub fn get(&self, day: u8) -> Option<String> {
+        fs::read_to_string(self.path_for(day)).ok()
+    }
+
+    pub fn put(&self, day: u8, input: &str) -> io::Result<()> {
+        write(&self.path_for(day), input)
+    }
+
+    /// The cached puzzle description for the day, if it has been downloaded before.
+    pub fn get_description(&self, day: u8) -> Option<String> {
+        fs::read_to_string(self.description_path(day)).ok()
+    }
+
+    pub fn put_description(&self, day: u8, description: &str) -> io::Result<()> {
+        write(&self.description_path(day), description)
+    }
+}
+
+fn write(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod input_cache_tests {
+    use super::*;
+    use temp_dir::TempDir;
+
+    fn temp_cache(name: &str) -> (TempDir, InputCache) {
+        let dir = TempDir::new(&format!("cache-{}", name));
+        let cache = InputCache::new(dir.path(), 2018);
+        (dir, cache)
+    }
+
+    #[test]
+    fn path_is_zero_padded_day_under_year() {
+        assert_eq!(InputCache::new("inputs", 2018).path_for(5), Path::new("inputs/2018/day05.txt"));
+        assert_eq!(InputCache::new("inputs", 2018).path_for(25), Path::new("inputs/2018/day25.txt"));
+        assert_eq!(InputCache::new("inputs", 2019).path_for(1), Path::new("inputs/2019/day01.txt"));
+        assert_eq!(InputCache::new("inputs", 2019).ledger_path(), Path::new("inputs/2019/ledger.tsv"));
+    }
+
+    #[test]
+    fn missing_day_is_not_cached() {
+        let (_dir, cache) = temp_cache("missing");
+        assert_eq!(cache.get(1), None);
+    }
+
+    #[test]
+    fn put_then_get_round_trips() {
+        let (_dir, cache) = temp_cache("round-trip");
+        cache.put(3, "#1 @ 1,3: 4x4\n").unwrap();
+        assert_eq!(cache.get(3), Some(String::from("#1 @ 1,3: 4x4\n")));
+    }
+
+    #[test]
+    fn descriptions_are_kept_beside_inputs() {
+        let (_dir, cache) = temp_cache("description");
+        cache.put_description(3, "## --- Day 3: No Matter How You Slice It ---\n").unwrap();
+        assert_eq!(cache.get_description(3), Some(String::from("## --- Day 3: No Matter How You Slice It ---\n")));
+        assert_eq!(cache.get(3), None);
+    }
+}
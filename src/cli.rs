@@ -0,0 +1,440 @@
+use advent2018::input::InputSource;
+use advent2018::logging::Level;
+use advent2018::report::Format;
+use advent2018::solver::Part;
+use std::time::Duration;
+
+/// What the binary has been asked to do.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub enum Command {
+    /// Solve a part and print the answer.
+    Solve,
+    /// Solve a part and submit the answer to the site.
+    Submit,
+    /// Check every day, or just the given one, against its recorded solutions.
+    Verify,
+    /// Time every day, or just the given one, against a saved baseline.
+    Bench,
+    /// Solve both parts of every day.
+    All,
+    /// Parse a day's input without solving, reporting every problem with it.
+    Validate,
+    /// Show a day's puzzle description.
+    Read,
+    /// Check every day, or just the given one, against the worked examples from its description.
+    Examples,
+    /// Generate and register the module for a new day.
+    New,
+}
+
+/// Options given to the binary on the command line.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub struct Options {
+    pub command: Command,
+    /// Which year's puzzles to work on, if not the profile's.
+    pub year: Option<u16>,
+    pub day: Option<String>,
+    pub part: Part,
+    pub input: InputSource,
+    /// How many times `bench` runs each phase.
+    pub runs: usize,
+    /// Save the `bench` results as the baseline for later runs.
+    pub save_baseline: bool,
+    /// How much to log to stderr, if not the profile's.
+    pub level: Option<Level>,
+    /// The config file profile to use.
+    pub profile: Option<String>,
+    /// How long requests to the site may take, if not the profile's.
+    pub timeout: Option<Duration>,
+    /// Wait for the puzzle to unlock before fetching its input.
+    pub wait: bool,
+    /// Save the worked examples from the day's description for `examples`, rather than run them.
+    pub extract: bool,
+    /// Download the new day's input and examples along with generating its module.
+    pub download: bool,
+    /// How answers are written to stdout when solving.
+    pub format: Format,
+}
+
+const DEFAULT_RUNS: usize = 10;
+
+/// The year worked on when neither `--year` nor the profile gives one.
+pub const DEFAULT_YEAR: u16 = 2018;
+
+/// The first year of Advent of Code.
+const FIRST_YEAR: u16 = 2015;
+
+pub const USAGE: &str = "\
+Usage: advent2018 [-v|-vv|-q] [--profile <name>] [--year <yyyy>] [--timeout <secs>] [submit] <day> [part] [--input <file>|-] [--refresh] [--wait]
+       advent2018 <day> [part] [--format text|json|csv]
+       advent2018 verify [day]
+       advent2018 bench [day] [--runs <n>] [--save-baseline]
+       advent2018 all [--format text|json|csv]
+       advent2018 validate <day> [--input <file>|-] [--refresh]
+       advent2018 read <day> [--refresh]
+       advent2018 examples [day] [--extract [--refresh]]
+       advent2018 new <day> [--download]";
+
+/// Parse the arguments following the program name.
+///
+pub fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut year = None;
+    let mut refresh = false;
+    let mut input_arg: Option<&str> = None;
+    let mut runs = None;
+    let mut save_baseline = false;
+    let mut level = None;
+    let mut profile = None;
+    let mut timeout = None;
+    let mut wait = false;
+    let mut extract = false;
+    let mut download = false;
+    let mut format = Format::Text;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" | "-y" => year = Some(args.next()
+                .and_then(|y| y.parse().ok())
+                .filter(|y| *y >= FIRST_YEAR)
+                .ok_or_else(|| format!("--year needs a year from {} on", FIRST_YEAR))?),
+            "--profile" | "-p" => profile = Some(args.next().ok_or("--profile needs a profile name")?.to_string()),
+            "--timeout" => timeout = Some(args.next()
+                .and_then(|t| t.parse().ok())
+                .filter(|t| *t > 0)
+                .map(Duration::from_secs)
+                .ok_or("--timeout needs a number of seconds")?),
+            "--refresh" => refresh = true,
+            "--wait" => wait = true,
+            "--extract" => extract = true,
+            "--download" => download = true,
+            "--format" | "-f" => format = args.next().ok_or("--format needs one of text, json or csv")?.parse()?,
+            "--input" | "-i" => input_arg = Some(args.next().ok_or("--input needs a file, or - for stdin")?),
+            "--runs" => runs = Some(args.next()
+                .and_then(|n| n.parse().ok())
+                .filter(|n| *n > 0)
+                .ok_or("--runs needs a number of runs")?),
+            "--save-baseline" => save_baseline = true,
+            "-v" | "--verbose" => level = Some(level.unwrap_or(Level::Info).more_verbose(1)),
+            "-vv" => level = Some(level.unwrap_or(Level::Info).more_verbose(2)),
+            "-q" | "--quiet" => level = Some(Level::Error),
+            flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'", flag)),
+            value => positional.push(value),
+        }
+    }
+
+    let command = match positional.first() {
+        Some(&"submit") => {
+            positional.remove(0);
+            Command::Submit
+        }
+        Some(&"verify") => {
+            positional.remove(0);
+            Command::Verify
+        }
+        Some(&"bench") => {
+            positional.remove(0);
+            Command::Bench
+        }
+        Some(&"all") => {
+            positional.remove(0);
+            Command::All
+        }
+        Some(&"validate") => {
+            positional.remove(0);
+            Command::Validate
+        }
+        Some(&"read") => {
+            positional.remove(0);
+            Command::Read
+        }
+        Some(&"examples") => {
+            positional.remove(0);
+            Command::Examples
+        }
+        Some(&"new") => {
+            positional.remove(0);
+            Command::New
+        }
+        _ => Command::Solve,
+    };
+
+    let day = positional.first().map(|d| d.to_string());
+    if day.is_none() && command != Command::Verify && command != Command::Bench && command != Command::All && command != Command::Examples {
+        return Err(String::from("A day argument is required"));
+    }
+    let part = positional.get(1).map(|p| p.parse()).unwrap_or(Ok(Part::One))?;
+    if command == Command::All && day.is_some() {
+        return Err(String::from("all runs every day, so takes no day argument"));
+    }
+    if command == Command::Verify && (positional.len() > 1 || input_arg.is_some() || refresh) {
+        return Err(String::from("verify checks both parts against the downloaded inputs, so takes no part, input or --refresh"));
+    }
+    if command == Command::Bench && positional.len() > 1 {
+        return Err(String::from("bench times both parts, so takes no part argument"));
+    }
+    if (runs.is_some() || save_baseline) && command != Command::Bench {
+        return Err(String::from("--runs and --save-baseline only apply to bench"));
+    }
+    if command == Command::Validate && positional.len() > 1 {
+        return Err(String::from("validate only parses the input, so takes no part argument"));
+    }
+    if command == Command::Read && (positional.len() > 1 || input_arg.is_some()) {
+        return Err(String::from("read shows every part of the puzzle, so takes no part or input"));
+    }
+    if command == Command::Examples && (positional.len() > 1 || input_arg.is_some()) {
+        return Err(String::from("examples runs the saved examples, so takes no part or input"));
+    }
+    if extract && (command != Command::Examples || day.is_none()) {
+        return Err(String::from("--extract only applies to examples for a given day"));
+    }
+    if command == Command::New && (positional.len() > 1 || input_arg.is_some()) {
+        return Err(String::from("new only generates a day, so takes no part or input"));
+    }
+    if download && command != Command::New {
+        return Err(String::from("--download only applies to new"));
+    }
+    if format != Format::Text && command != Command::Solve && command != Command::All {
+        return Err(String::from("--format only applies when solving a day or running all days"));
+    }
+    if wait && (input_arg.is_some() || (command != Command::Solve && command != Command::Submit)) {
+        return Err(String::from("--wait only applies when solving a day with its downloaded input"));
+    }
+    if positional.len() > 2 {
+        return Err(format!("Unexpected argument '{}'", positional[2]));
+    }
+
+    Ok(Options {
+        command,
+        year,
+        day,
+        part,
+        input: match input_arg {
+            Some(arg) => InputSource::from_arg(arg),
+            None => InputSource::Network { refresh },
+        },
+        runs: runs.unwrap_or(DEFAULT_RUNS),
+        save_baseline,
+        level,
+        profile,
+        timeout,
+        wait,
+        extract,
+        download,
+        format,
+    })
+}
+
+#[cfg(test)]
+mod parse_args_tests {
+    use super::*;
+
+    fn args(a: &[&str]) -> Vec<String> {
+        a.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn day_only_defaults_to_part_one() {
+        assert_eq!(parse_args(&args(&["5"])), Ok(Options {
+            command: Command::Solve,
+            year: None,
+            day: Some(String::from("5")),
+            part: Part::One,
+            input: InputSource::Network { refresh: false },
+            runs: DEFAULT_RUNS,
+            save_baseline: false,
+            level: None,
+            profile: None,
+            timeout: None,
+            wait: false,
+            extract: false,
+            download: false,
+            format: Format::Text,
+        }));
+    }
+
+    #[test]
+    fn day_part_and_refresh() {
+        assert_eq!(parse_args(&args(&["--refresh", "3", "2"])), Ok(Options {
+            command: Command::Solve,
+            year: None,
+            day: Some(String::from("3")),
+            part: Part::Two,
+            input: InputSource::Network { refresh: true },
+            runs: DEFAULT_RUNS,
+            save_baseline: false,
+            level: None,
+            profile: None,
+            timeout: None,
+            wait: false,
+            extract: false,
+            download: false,
+            format: Format::Text,
+        }));
+    }
+
+    #[test]
+    fn submit_day_and_part() {
+        assert_eq!(parse_args(&args(&["submit", "4", "2"])), Ok(Options {
+            command: Command::Submit,
+            year: None,
+            day: Some(String::from("4")),
+            part: Part::Two,
+            input: InputSource::Network { refresh: false },
+            runs: DEFAULT_RUNS,
+            save_baseline: false,
+            level: None,
+            profile: None,
+            timeout: None,
+            wait: false,
+            extract: false,
+            download: false,
+            format: Format::Text,
+        }));
+    }
+
+    #[test]
+    fn verify_needs_no_day() {
+        assert_eq!(parse_args(&args(&["verify"])).map(|o| (o.command, o.day)), Ok((Command::Verify, None)));
+        assert_eq!(parse_args(&args(&["verify", "3"])).map(|o| (o.command, o.day)),
+                   Ok((Command::Verify, Some(String::from("3")))));
+    }
+
+    #[test]
+    fn verify_takes_no_part_or_input() {
+        let expected = Err(String::from("verify checks both parts against the downloaded inputs, so takes no part, input or --refresh"));
+        assert_eq!(parse_args(&args(&["verify", "3", "2"])), expected);
+        assert_eq!(parse_args(&args(&["verify", "3", "-i", "nofile.txt"])), expected);
+        assert_eq!(parse_args(&args(&["verify", "--refresh"])), expected);
+    }
+
+    #[test]
+    fn bench_runs_and_baseline() {
+        let options = parse_args(&args(&["bench", "--runs", "25", "--save-baseline"])).unwrap();
+        assert_eq!((options.command, options.day, options.runs, options.save_baseline), (Command::Bench, None, 25, true));
+        assert_eq!(parse_args(&args(&["bench", "--runs", "0"])), Err(String::from("--runs needs a number of runs")));
+        assert_eq!(parse_args(&args(&["bench", "5", "2"])), Err(String::from("bench times both parts, so takes no part argument")));
+        assert_eq!(parse_args(&args(&["5", "--runs", "10"])), Err(String::from("--runs and --save-baseline only apply to bench")));
+        assert_eq!(parse_args(&args(&["all", "--save-baseline"])), Err(String::from("--runs and --save-baseline only apply to bench")));
+    }
+
+    #[test]
+    fn all_takes_no_day() {
+        assert_eq!(parse_args(&args(&["all"])).map(|o| (o.command, o.day)), Ok((Command::All, None)));
+        assert_eq!(parse_args(&args(&["all", "3"])), Err(String::from("all runs every day, so takes no day argument")));
+    }
+
+    #[test]
+    fn validate_takes_a_day_but_no_part() {
+        assert_eq!(parse_args(&args(&["validate", "4", "-i", "day04.txt"])).map(|o| (o.command, o.day, o.input)),
+                   Ok((Command::Validate, Some(String::from("4")), InputSource::File("day04.txt".into()))));
+        assert_eq!(parse_args(&args(&["validate"])), Err(String::from("A day argument is required")));
+        assert_eq!(parse_args(&args(&["validate", "4", "2"])),
+                   Err(String::from("validate only parses the input, so takes no part argument")));
+    }
+
+    #[test]
+    fn year_defaults_and_can_be_given() {
+        assert_eq!(parse_args(&args(&["5"])).map(|o| o.year), Ok(None));
+        assert_eq!(parse_args(&args(&["--year", "2019", "5"])).map(|o| o.year), Ok(Some(2019)));
+        assert_eq!(parse_args(&args(&["all", "-y", "2015"])).map(|o| o.year), Ok(Some(2015)));
+        assert_eq!(parse_args(&args(&["5", "--year", "18"])), Err(String::from("--year needs a year from 2015 on")));
+        assert_eq!(parse_args(&args(&["5", "--year"])), Err(String::from("--year needs a year from 2015 on")));
+    }
+
+    #[test]
+    fn read_takes_only_a_day() {
+        assert_eq!(parse_args(&args(&["read", "5", "--refresh"])).map(|o| (o.command, o.day, o.input)),
+                   Ok((Command::Read, Some(String::from("5")), InputSource::Network { refresh: true })));
+        assert_eq!(parse_args(&args(&["read"])), Err(String::from("A day argument is required")));
+        assert_eq!(parse_args(&args(&["read", "5", "2"])),
+                   Err(String::from("read shows every part of the puzzle, so takes no part or input")));
+    }
+
+    #[test]
+    fn examples_for_every_day_or_extracted_for_one() {
+        assert_eq!(parse_args(&args(&["examples"])).map(|o| (o.command, o.day, o.extract)), Ok((Command::Examples, None, false)));
+        assert_eq!(parse_args(&args(&["examples", "3", "--extract"])).map(|o| (o.command, o.day, o.extract)),
+                   Ok((Command::Examples, Some(String::from("3")), true)));
+        assert_eq!(parse_args(&args(&["examples", "--extract"])),
+                   Err(String::from("--extract only applies to examples for a given day")));
+        assert_eq!(parse_args(&args(&["3", "--extract"])),
+                   Err(String::from("--extract only applies to examples for a given day")));
+        assert_eq!(parse_args(&args(&["examples", "3", "2"])),
+                   Err(String::from("examples runs the saved examples, so takes no part or input")));
+    }
+
+    #[test]
+    fn new_takes_a_day_and_can_download() {
+        assert_eq!(parse_args(&args(&["new", "6", "--download"])).map(|o| (o.command, o.day, o.download)),
+                   Ok((Command::New, Some(String::from("6")), true)));
+        assert_eq!(parse_args(&args(&["new"])), Err(String::from("A day argument is required")));
+        assert_eq!(parse_args(&args(&["new", "6", "1"])), Err(String::from("new only generates a day, so takes no part or input")));
+        assert_eq!(parse_args(&args(&["6", "--download"])), Err(String::from("--download only applies to new")));
+    }
+
+    #[test]
+    fn format_for_solving_and_all() {
+        assert_eq!(parse_args(&args(&["5", "--format", "json"])).map(|o| o.format), Ok(Format::Json));
+        assert_eq!(parse_args(&args(&["all", "-f", "csv"])).map(|o| o.format), Ok(Format::Csv));
+        assert_eq!(parse_args(&args(&["5", "--format", "xml"])), Err(String::from("Unknown format 'xml', expected text, json or csv")));
+        assert_eq!(parse_args(&args(&["5", "--format"])), Err(String::from("--format needs one of text, json or csv")));
+        assert_eq!(parse_args(&args(&["submit", "5", "--format", "json"])),
+                   Err(String::from("--format only applies when solving a day or running all days")));
+    }
+
+    #[test]
+    fn verbosity_flags() {
+        assert_eq!(parse_args(&args(&["5"])).map(|o| o.level), Ok(None));
+        assert_eq!(parse_args(&args(&["5", "-v"])).map(|o| o.level), Ok(Some(Level::Debug)));
+        assert_eq!(parse_args(&args(&["-vv", "5"])).map(|o| o.level), Ok(Some(Level::Trace)));
+        assert_eq!(parse_args(&args(&["5", "-q"])).map(|o| o.level), Ok(Some(Level::Error)));
+    }
+
+    #[test]
+    fn timeout_in_seconds() {
+        assert_eq!(parse_args(&args(&["5", "--timeout", "5"])).map(|o| o.timeout), Ok(Some(Duration::from_secs(5))));
+        assert_eq!(parse_args(&args(&["5", "--timeout", "0"])), Err(String::from("--timeout needs a number of seconds")));
+    }
+
+    #[test]
+    fn wait_only_for_downloaded_input() {
+        assert_eq!(parse_args(&args(&["submit", "5", "--wait"])).map(|o| o.wait), Ok(true));
+        assert_eq!(parse_args(&args(&["5", "--wait", "-i", "day05.txt"])),
+                   Err(String::from("--wait only applies when solving a day with its downloaded input")));
+        assert_eq!(parse_args(&args(&["all", "--wait"])),
+                   Err(String::from("--wait only applies when solving a day with its downloaded input")));
+    }
+
+    #[test]
+    fn profile_by_name() {
+        assert_eq!(parse_args(&args(&["--profile", "alice", "5"])).map(|o| o.profile), Ok(Some(String::from("alice"))));
+        assert_eq!(parse_args(&args(&["5", "-p"])), Err(String::from("--profile needs a profile name")));
+    }
+
+    #[test]
+    fn input_from_file_or_stdin() {
+        assert_eq!(parse_args(&args(&["4", "--input", "day04.txt"])).map(|o| o.input),
+                   Ok(InputSource::File("day04.txt".into())));
+        assert_eq!(parse_args(&args(&["4", "2", "-i", "-"])).map(|o| o.input), Ok(InputSource::Stdin));
+    }
+
+    #[test]
+    fn input_without_value_should_produce_err_result() {
+        assert_eq!(parse_args(&args(&["4", "--input"])), Err(String::from("--input needs a file, or - for stdin")));
+    }
+
+    #[test]
+    fn missing_day_should_produce_err_result() {
+        assert_eq!(parse_args(&args(&[])), Err(String::from("A day argument is required")));
+        assert_eq!(parse_args(&args(&["submit"])), Err(String::from("A day argument is required")));
+    }
+
+    #[test]
+    fn unknown_option_should_produce_err_result() {
+        assert_eq!(parse_args(&args(&["1", "--bogus"])), Err(String::from("Unknown option '--bogus'")));
+    }
+}
@@ -0,0 +1,217 @@
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use cache::DEFAULT_CACHE_DIR;
+use logging::Level;
+
+/// The profile used when no `--profile` is given.
+///
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Settings for one user of the tool, from a `[name]` section of the config file.
+///
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Profile {
+    pub name: String,
+    /// The session token for the user's account.
+    pub session: Option<String>,
+    /// The year worked on when no `--year` is given.
+    pub year: Option<u16>,
+    /// How much to log when no `-v` or `-q` is given.
+    pub level: Option<Level>,
+    /// Where the profile's inputs and answers are kept.
+    pub cache_dir: Option<PathBuf>,
+    /// How long requests to the site may take when no `--timeout` is given.
+    pub timeout: Option<Duration>,
+}
+
+impl Profile {
+    fn new(name: &str) -> Profile {
+        Profile { name: name.to_string(), session: None, year: None, level: None, cache_dir: None, timeout: None }
+    }
+
+    /// Where the profile's inputs and answers are kept. Each profile gets its own by default, as
+    /// inputs differ between accounts.
+    pub fn cache_dir(&self) -> PathBuf {
+        match self.cache_dir {
+            Some(ref dir) => dir.clone(),
+            None if self.name == DEFAULT_PROFILE => PathBuf::from(DEFAULT_CACHE_DIR),
+            None => Path::new(DEFAULT_CACHE_DIR).join(&self.name),
+        }
+    }
+}
+
+/// The profiles read from the config file, which looks like:
+///
+/// ```text
+/// # settings before any [section] belong to the default profile
+/// session = 53616c7465...
+/// year = 2018
+///
+/// [alice]
+/// session = 6b6579...
+/// verbosity = debug
+/// cache = /home/alice/aoc-inputs
+/// timeout = 60
+/// ```
+///
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Config {
+    profiles: Vec<Profile>,
+}
+
+impl Config {
+    /// Read the config file at `path`. A missing file is an empty config.
+    pub fn load(path: &Path) -> Result<Config, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Config::parse(&contents).map_err(|e| format!("{}: {}", path.display(), e))?),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(From::from(e)),
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Config, String> {
+        let mut profiles = vec![Profile::new(DEFAULT_PROFILE)];
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                let name = line[1..line.len() - 1].trim();
+                if name.is_empty() {
+                    return Err(format!("line {}: a profile needs a name", i + 1));
+                }
+                if !profiles.iter().any(|p| p.name == name) {
+                    profiles.push(Profile::new(name));
+                }
+                continue;
+            }
+            let profile = profiles.last_mut().expect("There is always a default profile");
+            set_value(profile, line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        }
+        Ok(Config { profiles })
+    }
+
+    /// The named profile, or the default profile (which may be empty) when no name is given.
+    pub fn profile(&self, name: Option<&str>) -> Result<Profile, String> {
+        let wanted = name.unwrap_or(DEFAULT_PROFILE);
+        match self.profiles.iter().find(|p| p.name == wanted) {
+            Some(profile) => Ok(profile.clone()),
+            None if name.is_none() => Ok(Profile::new(DEFAULT_PROFILE)),
+            None => Err(format!("No profile '{}' in the config file", wanted)),
+        }
+    }
+}
+
+fn set_value(profile: &mut Profile, line: &str) -> Result<(), String> {
+    let mut parts = line.splitn(2, '=');
+    let key = parts.next().unwrap_or("").trim();
+    let value = parts.next().map(str::trim).ok_or_else(|| format!("Expected 'key = value' in '{}'", line))?;
+    match key {
+        "session" => profile.session = Some(value.to_string()),
+        "year" => profile.year = Some(value.parse().map_err(|_| format!("Bad year '{}'", value))?),
+        "verbosity" => profile.level = Some(value.parse()?),
+        "cache" => profile.cache_dir = Some(PathBuf::from(value)),
+        "timeout" => profile.timeout = Some(value.parse().map(Duration::from_secs).map_err(|_| format!("Bad timeout '{}', expected seconds", value))?),
+        _ => return Err(format!("Unknown setting '{}'", key)),
+    }
+    Ok(())
+}
+
+/// Where the config file is looked for: `$ADV_CONFIG`, otherwise `advent2018/config` in the XDG
+/// config directory.
+///
+pub fn config_path(adv_config: Option<String>, xdg_config_home: Option<String>, home: Option<String>) -> Option<PathBuf> {
+    if let Some(path) = adv_config.filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    let config_home = xdg_config_home
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home.filter(|p| !p.is_empty()).map(|h| Path::new(&h).join(".config")))?;
+    Some(config_home.join("advent2018").join("config"))
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+    use temp_dir::TempDir;
+
+    const CONFIG: &str = "\
+# shared machine
+session = default-token
+year = 2018
+
+[alice]
+session = alice-token
+verbosity = debug
+
+[bob]
+session = bob-token
+year = 2019
+cache = /tmp/bob
+timeout = 60
+";
+
+    #[test]
+    fn profiles_by_name() {
+        let config = Config::parse(CONFIG).unwrap();
+        assert_eq!(config.profile(Some("alice")), Ok(Profile {
+            name: String::from("alice"),
+            session: Some(String::from("alice-token")),
+            year: None,
+            level: Some(Level::Debug),
+            cache_dir: None,
+            timeout: None,
+        }));
+        assert_eq!(config.profile(Some("bob")).map(|p| (p.year, p.cache_dir(), p.timeout)),
+                   Ok((Some(2019), PathBuf::from("/tmp/bob"), Some(Duration::from_secs(60)))));
+        assert_eq!(config.profile(Some("carol")), Err(String::from("No profile 'carol' in the config file")));
+    }
+
+    #[test]
+    fn settings_before_any_section_are_the_default_profile() {
+        let profile = Config::parse(CONFIG).unwrap().profile(None).unwrap();
+        assert_eq!((profile.session, profile.year), (Some(String::from("default-token")), Some(2018)));
+    }
+
+    #[test]
+    fn empty_config_has_an_empty_default_profile() {
+        assert_eq!(Config::default().profile(None), Ok(Profile::new(DEFAULT_PROFILE)));
+    }
+
+    #[test]
+    fn each_profile_caches_inputs_separately() {
+        assert_eq!(Profile::new(DEFAULT_PROFILE).cache_dir(), PathBuf::from("inputs"));
+        assert_eq!(Profile::new("alice").cache_dir(), PathBuf::from("inputs/alice"));
+    }
+
+    #[test]
+    fn bad_settings_should_produce_err_result() {
+        assert_eq!(Config::parse("[a]\nyear = soon"), Err(String::from("line 2: Bad year 'soon'")));
+        assert_eq!(Config::parse("colour = blue"), Err(String::from("line 1: Unknown setting 'colour'")));
+        assert_eq!(Config::parse("session"), Err(String::from("line 1: Expected 'key = value' in 'session'")));
+        assert_eq!(Config::parse("[]"), Err(String::from("line 1: a profile needs a name")));
+        assert_eq!(Config::parse("verbosity = loud"),
+                   Err(String::from("line 1: Unknown verbosity 'loud', expected error, info, debug or trace")));
+    }
+
+    #[test]
+    fn missing_file_is_an_empty_config() {
+        let dir = TempDir::new("no-config");
+        assert_eq!(Config::load(&dir.join("config")).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn path_from_environment() {
+        let s = |v: &str| Some(v.to_string());
+        assert_eq!(config_path(s("/etc/aoc"), s("/x"), s("/home/a")), Some(PathBuf::from("/etc/aoc")));
+        assert_eq!(config_path(None, s("/x"), s("/home/a")), Some(PathBuf::from("/x/advent2018/config")));
+        assert_eq!(config_path(None, s(""), s("/home/a")), Some(PathBuf::from("/home/a/.config/advent2018/config")));
+        assert_eq!(config_path(None, None, None), None);
+    }
+}
@@ -1,32 +1,20 @@
-use std::str::FromStr;
-use std::num::ParseIntError;
 use std::collections::HashSet;
+use parse::{self, ParseError};
+use solver::{Answer, SolveError, Solver};
 
-fn split_frequencies(freqs: &String) -> Vec<&str> {
-    freqs.split('\n')
-        .map(|f| f.trim())
-        .collect()
+fn parse_frequencies(input: &str) -> Result<Vec<i32>, Vec<ParseError>> {
+    parse::lines(input)
 }
 
-fn to_i(freq: &str) -> Result<i32, ParseIntError> {
-    i32::from_str(freq.trim())
+fn solve_part_one(input: &str) -> Result<Answer, SolveError> {
+    let result: i32 = parse_frequencies(input)?.iter().sum();
+    Ok(result.into())
 }
 
-pub fn solve(input: &String) -> String {
-    let result: i32  = split_frequencies(&input)
-        .iter()
-        .filter(|f| !f.is_empty())
-        .map(|f| to_i(*f).expect("Could not parse {}"))
-        .sum();
-    format!("{}", result)
-}
-
-pub fn solve_part_two(input: &String) -> String {
+fn solve_part_two(input: &str) -> Result<Answer, SolveError> {
     let mut seen: HashSet<i32> = HashSet::new();
-    let first_match = split_frequencies(&input)
+    let first_match = parse_frequencies(input)?
         .iter()
-        .filter(|f| !f.is_empty())
-        .map(|f| to_i(*f).expect("Could not parse {}"))
         .cycle()
         .try_fold(0,
                   |acc, f| {
@@ -38,26 +26,40 @@ pub fn solve_part_two(input: &String) -> String {
                       }
                   });
     match first_match {
-        Ok(_) => "Not found!".to_string(),
-        Err(x) => x.to_string()
+        Ok(_) => Err(SolveError::NoSolution(String::from("No frequency is reached twice"))),
+        Err(x) => Ok(x.into())
     }
 
 }
 
-
 #[cfg(test)]
-mod split_freqs_tests {
+mod resulting_frequency_tests {
     use super::*;
+    use parse::ParseErrorKind;
 
     #[test]
-    fn split_frequencies_for_one_freq() {
-        assert_eq!(split_frequencies(&String::from("+100")), ["+100"]);
+    fn example_test() {
+        assert_eq!(solve_part_one("+1\n-2\n+3\n+1").unwrap(), "3");
+        assert_eq!(solve_part_one("-1\n-2\n-3").unwrap(), "-6");
     }
 
     #[test]
-    fn split_frequencies_for_mult_freqs() {
-        assert_eq!(split_frequencies(&String::from("+100\n-100\n+10")),
-                   ["+100", "-100", "+10"]);
+    fn bad_frequency_should_produce_err_result() {
+        assert_eq!(solve_part_one("+1\n\nfoo\n+x"),
+                   Err(SolveError::Parse(vec![
+                       ParseError {
+                           kind: ParseErrorKind::BadNumber(String::from("foo")),
+                           line: Some(3),
+                           column: Some(0),
+                           text: String::from("foo"),
+                       },
+                       ParseError {
+                           kind: ParseErrorKind::BadNumber(String::from("+x")),
+                           line: Some(4),
+                           column: Some(0),
+                           text: String::from("+x"),
+                       },
+                   ])));
     }
 }
 
@@ -67,11 +69,33 @@ mod first_frequency_reaches_twice_tests {
 
     #[test]
     fn example_test() {
-        assert_eq!(solve_part_two(&String::from("+1\n-2\n+3\n+1")), "2");
-        assert_eq!(solve_part_two(&String::from("+3\n+3\n+4\n-2\n-4")), "10");
-        assert_eq!(solve_part_two(&String::from("-6\n+3\n+8\n+5\n-6")), "5");
-        assert_eq!(solve_part_two(&String::from("+7\n+7\n-2\n-7\n-4")), "14");
+        assert_eq!(solve_part_two("+1\n-2\n+3\n+1").unwrap(), "2");
+        assert_eq!(solve_part_two("+3\n+3\n+4\n-2\n-4").unwrap(), "10");
+        assert_eq!(solve_part_two("-6\n+3\n+8\n+5\n-6").unwrap(), "5");
+        assert_eq!(solve_part_two("+7\n+7\n-2\n-7\n-4").unwrap(), "14");
     }
 }
 
+pub struct Day01;
+
+impl Solver for Day01 {
+    fn day(&self) -> u8 {
+        1
+    }
 
+    fn title(&self) -> &'static str {
+        "Chronal Calibration"
+    }
+
+    fn parse(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(parse_frequencies(input)?.len())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part_two(input)
+    }
+}
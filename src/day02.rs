@@ -1,4 +1,6 @@
 use std::collections::HashMap;
+use parse::{self, ParseError, ParseErrorKind};
+use solver::{Answer, SolveError, Solver};
 
 fn count_repeats(input: &str) -> (i32, i32) {
     let mut char_counts: HashMap<char,i8> = HashMap::new();
@@ -49,13 +51,13 @@ mod count_repeats_test {
     }
 }
 
-fn calc_checksum(lines: &Vec<&str>) -> i32 {
-    let (twos, threes) = lines.into_iter()
+fn calc_checksum(lines: &[&str]) -> i32 {
+    let (twos, threes) = lines.iter()
         .map(|s| count_repeats(s))
         .fold((0,0), |(acc_twos, acc_threes), (twos, threes)| {
             (acc_twos + twos, acc_threes + threes)
         });
-    (twos * threes) as i32
+    twos * threes
 }
 
 #[cfg(test)]
@@ -64,41 +66,86 @@ mod calc_checksum_tests {
 
     #[test]
     fn no_lines_should_be_zero_checksum() {
-        assert_eq!(calc_checksum(&vec!{}), 0)
+        assert_eq!(calc_checksum(&[]), 0)
     }
 
     #[test]
     fn single_line_no_two_or_threes_checksum_should_be_zero() {
-        assert_eq!(calc_checksum(&vec!{"abcde"}), 0)
+        assert_eq!(calc_checksum(&["abcde"]), 0)
     }
 
     #[test]
     fn single_line_with_one_two_checksum_should_be_zero() {
-        assert_eq!(calc_checksum(&vec!{"aabcde"}), 0)
+        assert_eq!(calc_checksum(&["aabcde"]), 0)
     }
 
 
 
     #[test]
     fn problem_example_test() {
-        assert_eq!(calc_checksum(&vec!{"abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab"}), 12)
+        assert_eq!(calc_checksum(&["abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab"]), 12)
     }
 
 }
 
-fn split_input_lines(input: &String) -> Vec<&str> {
-    input.split("\n").map(str::trim).filter(|s| !s.is_empty()).collect()
+/// Read the box IDs, reporting every character that is not a lowercase letter, and every ID that
+/// is not as long as the first.
+///
+fn parse_box_ids(input: &str) -> Result<Vec<&str>, Vec<ParseError>> {
+    let lines = parse::numbered_lines(input);
+    let length = lines.first().map_or(0, |(_, id)| id.len());
+    let mut errors = Vec::new();
+    for (n, id) in &lines {
+        let before = errors.len();
+        for (column, c) in id.char_indices().filter(|(_, c)| !c.is_ascii_lowercase()) {
+            errors.push(ParseError::new(ParseErrorKind::UnexpectedCharacter(c, "a lowercase letter"), id)
+                .at_line(*n)
+                .at_column(column));
+        }
+        if errors.len() == before && id.len() != length {
+            errors.push(ParseError::new(ParseErrorKind::MalformedLine("a box ID as long as the first"), id)
+                .at_line(*n)
+                .at_column(id.len().min(length)));
+        }
+    }
+    if errors.is_empty() { Ok(lines.into_iter().map(|(_, id)| id).collect()) } else { Err(errors) }
 }
 
-pub fn solve_part_one(input: &String) -> String {
-    let lines: Vec<&str> = split_input_lines(input);
-    format!("{}", calc_checksum(&lines))
+#[cfg(test)]
+mod parse_box_ids_tests {
+    use super::*;
+
+    #[test]
+    fn ids_of_lowercase_letters() {
+        assert_eq!(parse_box_ids("abcde\nfghij\n"), Ok(vec!["abcde", "fghij"]));
+    }
+
+    #[test]
+    fn ids_of_different_lengths_should_produce_err_result() {
+        assert_eq!(parse_box_ids("abcde\nab\nfghij\nklmnop\n"), Err(vec![
+            ParseError::new(ParseErrorKind::MalformedLine("a box ID as long as the first"), "ab").at_line(2).at_column(2),
+            ParseError::new(ParseErrorKind::MalformedLine("a box ID as long as the first"), "klmnop").at_line(4).at_column(5),
+        ]));
+    }
+
+    #[test]
+    fn ids_with_other_characters_should_produce_err_result() {
+        assert_eq!(parse_box_ids("abcde\naBcdé\n"), Err(vec![
+            ParseError::new(ParseErrorKind::UnexpectedCharacter('B', "a lowercase letter"), "aBcdé").at_line(2).at_column(1),
+            ParseError::new(ParseErrorKind::UnexpectedCharacter('é', "a lowercase letter"), "aBcdé").at_line(2).at_column(4),
+        ]));
+    }
+}
+
+fn solve_part_one(input: &str) -> Result<Answer, SolveError> {
+    let lines: Vec<&str> = parse_box_ids(input)?;
+    Ok(calc_checksum(&lines).into())
 }
 
 
 
-pub fn solve_part_two(input: &String) -> String {
-    let lines: &Vec<&str> = &split_input_lines(input);
+fn solve_part_two(input: &str) -> Result<Answer, SolveError> {
+    let lines: &[&str] = &parse_box_ids(input)?;
 
     let default_sim = &String::new();
     let mut most_similar = String::new();
@@ -123,7 +170,11 @@ pub fn solve_part_two(input: &String) -> String {
         }
     }
 
-    most_similar
+    if most_similar.is_empty() {
+        Err(SolveError::NoSolution(String::from("No two box IDs share any letters")))
+    } else {
+        Ok(most_similar.into())
+    }
 }
 
 
@@ -133,6 +184,52 @@ mod solve_part_two_tests {
 
     #[test]
     fn acceptance_test() {
-        assert_eq!(solve_part_two(&String::from("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz\n")), "fgij")
+        assert_eq!(solve_part_two("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz\n").unwrap(), "fgij")
+    }
+
+    #[test]
+    fn ragged_ids_are_a_parse_error() {
+        assert!(matches!(solve_part_two("abcde\nab\nfghij"), Err(SolveError::Parse(_))));
+    }
+}
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Inventory Management System"
+    }
+
+    fn parse(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(parse_box_ids(input)?.len())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod validate_tests {
+    use super::*;
+
+    #[test]
+    fn box_ids_are_counted() {
+        assert_eq!(Day02.parse("abcde\nfghij\n"), Ok(2));
+    }
+
+    #[test]
+    fn ragged_ids_are_reported() {
+        assert_eq!(Day02.parse("abcde\nab\nfghij"), Err(SolveError::Parse(vec![
+            ParseError::new(ParseErrorKind::MalformedLine("a box ID as long as the first"), "ab").at_line(2).at_column(2),
+        ])));
     }
 }
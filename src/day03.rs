@@ -1,19 +1,19 @@
-use regex::Regex;
-use std::str::FromStr;
 use std::collections::HashSet;
 use std::collections::HashMap;
-
-fn split_input_lines(input: &String) -> Vec<&str> {
-    input.split("\n").map(str::trim).filter(|s| !s.is_empty()).collect()
-}
-
-#[derive(PartialEq, Eq, Debug, Clone, Hash)]
-struct RectangleDescriptor {
-    id: String,
-    pos_left: usize,
-    pos_top: usize,
-    size_x: usize,
-    size_y: usize,
+use parse::{self, ParseError};
+use solver::{Answer, SolveError, Solver};
+
+regex_record! {
+    #[derive(PartialEq, Eq, Debug, Clone, Hash)]
+    struct RectangleDescriptor {
+        id: String,
+        pos_left: usize,
+        pos_top: usize,
+        size_x: usize,
+        size_y: usize,
+    }
+    from r"^#(?P<id>\d+) @ (?P<pos_left>\d+),(?P<pos_top>\d+): (?P<size_x>\d+)x(?P<size_y>\d+)"
+    expecting "a claim like '#1 @ 1,3: 4x4'"
 }
 
 impl RectangleDescriptor {
@@ -51,39 +51,22 @@ mod squares_for_rectangle_tests {
     }
 }
 
-fn parse_rectangle_descriptor(descriptor: &str) -> Result<RectangleDescriptor, String> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"^#(\d+) @ (\d+),(\d+): (\d+)x(\d+)").unwrap();
-    }
-
-    if RE.is_match(descriptor) {
-        let cap = RE.captures(descriptor).unwrap();
-        return Ok(RectangleDescriptor {
-            id: String::from(cap.get(1).unwrap().as_str()),
-            pos_left: usize::from_str(cap.get(2).unwrap().as_str()).unwrap(),
-            pos_top: usize::from_str(cap.get(3).unwrap().as_str()).unwrap(),
-            size_x: usize::from_str(cap.get(4).unwrap().as_str()).unwrap(),
-            size_y: usize::from_str(cap.get(5).unwrap().as_str()).unwrap(),
-        });
-    } else {
-        return Err(format!("Failed to parse rectangle descriptor: [{}]", descriptor));
-    }
-}
-
 #[cfg(test)]
 mod parser_tests {
     use super::*;
+    use parse::ParseErrorKind;
+    use std::str::FromStr;
 
     #[test]
     fn parse_examples_successful() {
-        assert_eq!(parse_rectangle_descriptor("#1 @ 1,3: 4x4").unwrap(), RectangleDescriptor {
+        assert_eq!(RectangleDescriptor::from_str("#1 @ 1,3: 4x4").unwrap(), RectangleDescriptor {
             id: String::from("1"),
             pos_left: 1,
             pos_top: 3,
             size_x: 4,
             size_y: 4,
         });
-        assert_eq!(parse_rectangle_descriptor("#1043 @ 674,568: 12x17").unwrap(), RectangleDescriptor {
+        assert_eq!(RectangleDescriptor::from_str("#1043 @ 674,568: 12x17").unwrap(), RectangleDescriptor {
             id: String::from("1043"),
             pos_left: 674,
             pos_top: 568,
@@ -95,62 +78,74 @@ mod parser_tests {
 
     #[test]
     fn parse_examples_fail() {
-        assert_eq!(parse_rectangle_descriptor("rubbish"), Err(String::from("Failed to parse rectangle descriptor: [rubbish]")));
+        assert_eq!(RectangleDescriptor::from_str("rubbish"),
+                   Err(ParseError::new(ParseErrorKind::MalformedLine("a claim like '#1 @ 1,3: 4x4'"), "rubbish").at_column(0)));
+        assert_eq!(RectangleDescriptor::from_str("#1 @ 99999999999999999999,3: 4x4"),
+                   Err(ParseError::new(ParseErrorKind::BadNumber(String::from("99999999999999999999")), "#1 @ 99999999999999999999,3: 4x4")
+                       .at_column(5)));
     }
 }
 
 
-pub fn solve_part_one(input: &String) -> String {
-    let lines = split_input_lines(input);
-    println!("Got line input -> {} lines", lines.len());
-    let (_, dupes) = lines.iter()
-        .map(|line: &&str| {
-            parse_rectangle_descriptor(line).expect(line).squares()
-        })
+fn parse_rectangles(input: &str) -> Result<Vec<RectangleDescriptor>, Vec<ParseError>> {
+    parse::lines(input)
+}
+
+fn solve_part_one(input: &str) -> Result<Answer, SolveError> {
+    let rectangles = parse_rectangles(input)?;
+    debug!("Got line input -> {} lines", rectangles.len());
+    let (_, dupes) = rectangles.iter()
+        .map(|rect| rect.squares())
         .fold((HashSet::<(usize, usize)>::new(), HashSet::<(usize, usize)>::new()),
               |(all_squares, dupes), squares| {
-                  let new_dupes: HashSet<(usize, usize)> = all_squares.intersection(&squares).map(|s| *s).collect();
-                  let all_dupes: HashSet<(usize, usize)> = dupes.union(&new_dupes).map(|s| *s).collect();
+                  let new_dupes: HashSet<(usize, usize)> = all_squares.intersection(&squares).copied().collect();
+                  let all_dupes: HashSet<(usize, usize)> = dupes.union(&new_dupes).copied().collect();
 
-                  let new_all_squares: HashSet<(usize, usize)> = all_squares.union(&squares).map(|s| *s).collect();
+                  let new_all_squares: HashSet<(usize, usize)> = all_squares.union(&squares).copied().collect();
 
                   (new_all_squares, all_dupes)
               });
 
-    dupes.len().to_string()
+    Ok(dupes.len().into())
 }
 
 
 #[cfg(test)]
 mod part_one_tests {
     use super::*;
+    use parse::ParseErrorKind;
+
+    #[test]
+    fn malformed_claims_are_reported_with_their_line_and_column() {
+        let malformed = |text, line, column| {
+            ParseError::new(ParseErrorKind::MalformedLine("a claim like '#1 @ 1,3: 4x4'"), text).at_line(line).at_column(column)
+        };
+        assert_eq!(solve_part_one("#1 @ 1,3: 4x4\n#12 @ 3,x: 4x4\n#3 5,5: 2x2\n"),
+                   Err(SolveError::Parse(vec![malformed("#12 @ 3,x: 4x4", 2, 8), malformed("#3 5,5: 2x2", 3, 3)])));
+    }
 
     #[test]
     fn acceptance_tests() {
-        assert_eq!(solve_part_one(&String::from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n")), "4")
+        assert_eq!(solve_part_one("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n").unwrap(), "4")
     }
 }
 
-pub fn solve_part_two(input: &String) -> String {
-    let lines = split_input_lines(input);
-    println!("Split all lines...");
-    let rectangles: Vec<RectangleDescriptor> = lines.iter()
-        .map(|line| {
-            parse_rectangle_descriptor(line).expect(line)
-        }).collect();
+fn solve_part_two(input: &str) -> Result<Answer, SolveError> {
+    let rectangles = parse_rectangles(input)?;
+    debug!("Split all lines...");
     let squares = rectangles.iter().map(|r| r.squares());
     let squares_and_rects = rectangles.iter().zip(squares)
         .fold(HashMap::<&RectangleDescriptor, HashSet<(usize, usize)>>::new(), |mut acc, (rect, squares)| {
-            acc.insert(&rect, squares);
+            acc.insert(rect, squares);
             acc
         });
 
     let rectangles_count = squares_and_rects.len();
 
-    println!("Parsed all rectangles: {}", rectangles_count);
+    debug!("Parsed all rectangles: {}", rectangles_count);
 
     for rect in rectangles.iter() {
-        println!("{}:", rect.id);
+        trace!("{}:", rect.id);
         let rect_squares = squares_and_rects.get(rect).unwrap();
         let mut has_overlap = false;
 
@@ -158,19 +153,19 @@ pub fn solve_part_two(input: &String) -> String {
             let other_squares = squares_and_rects.get(other).unwrap();
 
             if rect != other && !rect_squares.is_disjoint(other_squares) {
-                println!("{}, {} -> overaps!", rect.id, other.id);
+                trace!("{}, {} -> overlaps!", rect.id, other.id);
                 has_overlap = true;
                 break;
             }
         }
 
         if !has_overlap {
-            println!("{} has no overlaps!", rect.id);
-            return rect.id.to_string();
+            debug!("{} has no overlaps!", rect.id);
+            return Ok(Answer::parse(&rect.id));
         }
     }
 
-    String::from("Could not find Rectangle with no dupes!")
+    Err(SolveError::NoSolution(String::from("Could not find Rectangle with no dupes!")))
 }
 
 #[cfg(test)]
@@ -179,6 +174,30 @@ mod part_two_tests {
 
     #[test]
     fn acceptance_tests() {
-        assert_eq!(solve_part_two(&String::from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n")), "3")
+        assert_eq!(solve_part_two("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n").unwrap(), "3")
+    }
+}
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "No Matter How You Slice It"
+    }
+
+    fn parse(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(parse_rectangles(input)?.len())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part_two(input)
     }
 }
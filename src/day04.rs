@@ -1,10 +1,10 @@
-use chrono::{NaiveDateTime, NaiveDate, Timelike};
-use regex::Regex;
-use parse;
+use chrono::{NaiveDateTime, Timelike};
+use parse::{self, LinePattern, ParseError, ParseErrorKind};
 use std::collections::HashMap;
 use std::ops::Range;
 use std::str::FromStr;
 use std::cmp::Ordering;
+use solver::{Answer, SolveError, Solver};
 
 #[derive(PartialEq, Eq, Debug)]
 enum GuardEvent {
@@ -31,51 +31,39 @@ struct GuardRecord {
 }
 
 impl GuardEvent {
-    pub fn parse(message: &Message) -> Result<GuardEvent, String> {
+    pub fn parse(message: &Message) -> Result<GuardEvent, ParseError> {
         GuardEvent::parse_for_guard(String::from(""), message)
     }
 
-    pub fn parse_for_guard(guard: String, message: &Message) -> Result<GuardEvent, String> {
+    pub fn parse_for_guard(guard: String, message: &Message) -> Result<GuardEvent, ParseError> {
         lazy_static! {
-        static ref RE: Regex = Regex::new(r"((falls asleep)|(wakes up)|(Guard #(\d+) begins shift))$").unwrap();
+        static ref RE: LinePattern =
+            LinePattern::new(r"(?P<event>(falls asleep)|(wakes up)|(Guard #(?P<guard>\d+) begins shift))$").unwrap();
     }
 
-        let message_msg = message.message.to_string();
-
-        if RE.is_match(message_msg.as_str()) {
-            let cap = RE.captures(message_msg.as_str()).unwrap();
-
-            println!("{:?}", cap);
-
-            let parsed_guard = match cap.get(5) {
-                Some(m) => String::from(m.as_str()),
-                None => String::new()
-            };
-
-            let guard_id = if parsed_guard.is_empty() { guard } else { parsed_guard };
-            let guard_record = GuardRecord {
-                message: message.clone(),
-                guard: guard_id,
-            };
-            let event_desc = cap.get(1).unwrap().as_str();
-            Ok(
-                if event_desc == "falls asleep" {
-                    GuardEvent::Sleep(guard_record)
-                } else if event_desc == "wakes up" {
-                    GuardEvent::WakeUp(guard_record)
-                } else {
-                    GuardEvent::BeginShift(guard_record)
-                }
-            )
-        } else {
-            return Err(format!("Could not parse guard event '{}'", message_msg).to_string());
-        }
+        let fields = parse::fields(&RE, &message.message, "'falls asleep', 'wakes up' or 'Guard #10 begins shift'")?;
+
+        let parsed_guard = fields.get_opt::<u32>("guard")?;
+        trace!("{:?} {:?}", fields.str("event"), parsed_guard);
+
+        let guard_record = GuardRecord {
+            message: message.clone(),
+            guard: parsed_guard.map_or(guard, |id| id.to_string()),
+        };
+        Ok(
+            match fields.str("event") {
+                Some("falls asleep") => GuardEvent::Sleep(guard_record),
+                Some("wakes up") => GuardEvent::WakeUp(guard_record),
+                _ => GuardEvent::BeginShift(guard_record),
+            }
+        )
     }
 }
 
 #[cfg(test)]
 mod guard_event_tests {
     use super::*;
+    use chrono::NaiveDate;
 
     #[test]
     fn parse_begin_shift_guard_event() {
@@ -97,7 +85,8 @@ mod guard_event_tests {
             ts: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 0, 0),
         };
         assert_eq!(GuardEvent::parse(&invalid_message),
-                   Err(String::from("Could not parse guard event 'Gah, not an event!'")));
+                   Err(ParseError::new(ParseErrorKind::MalformedLine("'falls asleep', 'wakes up' or 'Guard #10 begins shift'"),
+                                       "Gah, not an event!").at_column(1)));
     }
 
 
@@ -130,15 +119,17 @@ mod guard_event_tests {
 
 type GuardSleepRanges = HashMap<String, Vec<Range<u32>>>;
 
-fn guard_sleep_times(guard_events: &Vec<GuardEvent>) -> GuardSleepRanges {
+fn guard_sleep_times(guard_events: &[GuardEvent]) -> GuardSleepRanges {
     let mut guard_sleep_times = HashMap::<String, Vec<Range<u32>>>::new();
 
     guard_events.iter().fold(None, |previous_event, event| {
-        let sleep_record = guard_sleep_times.entry(event.record().guard.to_string()).or_insert(Vec::<Range<u32>>::new());
+        let sleep_record = guard_sleep_times.entry(event.record().guard.to_string()).or_default();
         match event {
             GuardEvent::Sleep(_) => Some(event),
             GuardEvent::WakeUp(gr) => {
-                sleep_record.push(previous_event.unwrap().record().message.ts.minute()..gr.message.ts.minute());
+                if let Some(fell_asleep) = previous_event {
+                    sleep_record.push(fell_asleep.record().message.ts.minute()..gr.message.ts.minute());
+                }
                 None
             }
             _ => None
@@ -148,18 +139,30 @@ fn guard_sleep_times(guard_events: &Vec<GuardEvent>) -> GuardSleepRanges {
     guard_sleep_times
 }
 
-fn guard_with_most_times_asleep(sleep_times: &GuardSleepRanges) -> (String, &Vec<Range<u32>>) {
+fn guard_with_most_times_asleep(sleep_times: &GuardSleepRanges) -> Option<(String, &Vec<Range<u32>>)> {
     let mut sleep_times_flattened = sleep_times.iter().collect::<Vec<(&String, &Vec<Range<u32>>)>>();
     sleep_times_flattened.sort_by_key(|(_, sleep_ranges)| -> u32 {
         (*sleep_ranges).iter().map(|range| {
-            (range.end - 1) - range.start
+            range.end - range.start
         }).sum()
     });
-    let (guard, sleep_ranges) = sleep_times_flattened.last().unwrap();
-    (guard.to_string(), &sleep_ranges.clone())
+    sleep_times_flattened.last().map(|(guard, sleep_ranges)| (guard.to_string(), *sleep_ranges))
 }
 
-fn minute_most_asleep(sleep_ranges: &Vec<Range<u32>>) -> (u32,u32) {
+#[cfg(test)]
+mod guard_with_most_times_asleep_tests {
+    use super::*;
+
+    #[test]
+    fn every_minute_of_every_nap_counts() {
+        let mut sleep_times = GuardSleepRanges::new();
+        sleep_times.insert(String::from("10"), vec![0..4, 10..14, 20..24]);
+        sleep_times.insert(String::from("99"), vec![Range { start: 30, end: 41 }]);
+        assert_eq!(guard_with_most_times_asleep(&sleep_times).map(|(guard, _)| guard), Some(String::from("10")));
+    }
+}
+
+fn minute_most_asleep(sleep_ranges: &[Range<u32>]) -> (u32,u32) {
     let mut minute_counts = HashMap::<u32, u32>::new();
     for range in sleep_ranges.iter() {
         let r: Vec<u32> = range.clone().collect();
@@ -169,17 +172,21 @@ fn minute_most_asleep(sleep_ranges: &Vec<Range<u32>>) -> (u32,u32) {
         }
     }
     let max = minute_counts.iter()
-        .max_by_key(|c| { (*c).1 });
+        .max_by_key(|c| { c.1 });
     match max {
-        Some((minute, count)) => (minute.clone(), count.clone()),
+        Some((minute, count)) => (*minute, *count),
         None => (0, 0)
     }
 }
 
-#[derive(PartialEq, PartialOrd, Eq, Clone, Debug)]
-struct Message {
-    ts: NaiveDateTime,
-    message: String,
+regex_record! {
+    #[derive(PartialEq, Eq, Clone, Debug)]
+    struct Message {
+        ts: NaiveDateTime => |ts| NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M"),
+        message: String,
+    }
+    from r"^\[(?P<ts>[\d\-: ]+)\] (?P<message>.+)$"
+    expecting "a record like '[1518-11-01 00:00] falls asleep'"
 }
 
 impl Ord for Message {
@@ -188,36 +195,20 @@ impl Ord for Message {
     }
 }
 
-impl Message {
-    pub fn parse(record_text: &str) -> Result<Message, String> {
-        lazy_static! {
-        static ref RE: Regex = Regex::new(r"^\[([\d\-: ]+)\] (.+)$").unwrap();
-    }
-
-        if RE.is_match(record_text) {
-            let cap = RE.captures(record_text).unwrap();
-
-            if let Ok(dt) = NaiveDateTime::parse_from_str(cap.get(1).unwrap().as_str(), "%Y-%m-%d %H:%M") {
-                Ok(Message {
-                    ts: dt,
-                    message: String::from(cap.get(2).unwrap().as_str()),
-                })
-            } else {
-                Err(format!("Could not parse message datetime '{}'", record_text).to_string())
-            }
-        } else {
-            return Err(format!("Could not parse message '{}'", record_text).to_string());
-        }
+impl PartialOrd for Message {
+    fn partial_cmp(&self, other: &Message) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
 #[cfg(test)]
 mod message_tests {
     use super::*;
+    use chrono::NaiveDate;
 
     #[test]
     fn parse_valid_message() {
-        assert_eq!(Message::parse("[1518-11-01 00:00] Guard #10 begins shift").unwrap(),
+        assert_eq!(Message::from_str("[1518-11-01 00:00] Guard #10 begins shift").unwrap(),
                    Message {
                        ts: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 0, 0),
                        message: String::from("Guard #10 begins shift"),
@@ -226,45 +217,80 @@ mod message_tests {
 
     #[test]
     fn parse_invalid_message_should_produce_err_result() {
-        assert_eq!(Message::parse("[1518-13-01 00:00] Guard #10 begins shift"),
-                   Err(String::from("Could not parse message datetime '[1518-13-01 00:00] Guard #10 begins shift'")));
+        assert_eq!(Message::from_str("[1518-13-01 00:00] Guard #10 begins shift"),
+                   Err(ParseError::new(ParseErrorKind::BadTimestamp(String::from("1518-13-01 00:00")),
+                                       "[1518-13-01 00:00] Guard #10 begins shift").at_column(1)));
 
-        assert_eq!(Message::parse("[1518-11-01 00:00]"),
-                   Err(String::from("Could not parse message '[1518-11-01 00:00]'")));
+        assert_eq!(Message::from_str("[1518-11-01 00:00]"),
+                   Err(ParseError::new(ParseErrorKind::MalformedLine("a record like '[1518-11-01 00:00] falls asleep'"),
+                                       "[1518-11-01 00:00]").at_column(18)));
     }
 }
 
 
-pub fn solve_part_one(input: &String) -> String {
-    let guard_events = lines_to_guard_events(input);
+fn guard_id(guard: &str) -> Result<u32, ParseErrorKind> {
+    u32::from_str(guard).map_err(|_| ParseErrorKind::BadNumber(guard.to_string()))
+}
+
+fn solve_part_one(input: &str) -> Result<Answer, SolveError> {
+    let guard_events = lines_to_guard_events(input)?;
     let sleep_times = guard_sleep_times(&guard_events);
-    let (guard, sleep_ranges) = guard_with_most_times_asleep(&sleep_times);
+    let (guard, sleep_ranges) = guard_with_most_times_asleep(&sleep_times)
+        .ok_or_else(|| SolveError::NoSolution(String::from("No guard found!")))?;
     let (minute_most_slept_during, _) = minute_most_asleep(sleep_ranges);
-    (u32::from_str(&guard).unwrap() * minute_most_slept_during).to_string()
+    Ok((guard_id(&guard).map_err(|kind| ParseError::new(kind, &guard))? * minute_most_slept_during).into())
+}
+
+/// Check that the event can follow the one before it in time.
+///
+fn check_event_order(previous: Option<&GuardEvent>, event: &GuardEvent) -> Result<(), ParseErrorKind> {
+    let reason = match (previous, event) {
+        (None, GuardEvent::BeginShift(_)) => return Ok(()),
+        (None, _) => "the first record must be a guard beginning their shift",
+        (Some(GuardEvent::Sleep(_)), GuardEvent::BeginShift(_)) => "a shift begins while the last guard is asleep",
+        (Some(GuardEvent::Sleep(_)), GuardEvent::Sleep(_)) => "the guard falls asleep while already asleep",
+        (Some(GuardEvent::Sleep(asleep)), GuardEvent::WakeUp(awake))
+            if awake.message.ts.minute() <= asleep.message.ts.minute() => "the guard wakes up no later than they fell asleep",
+        (Some(GuardEvent::Sleep(_)), GuardEvent::WakeUp(_)) => return Ok(()),
+        (Some(_), GuardEvent::WakeUp(_)) => "the guard wakes up without having fallen asleep",
+        (Some(_), _) => return Ok(()),
+    };
+    Err(ParseErrorKind::UnexpectedEventOrder(reason.to_string()))
 }
 
-fn lines_to_guard_events(input: &String) -> Vec<GuardEvent> {
-    let lines = parse::split_input_lines(input);
-    let mut messages: Vec<Message> =
-        lines.iter().map(|line| {
-            Message::parse(line).expect(format!("Could not parse guard event! '{}'", line).as_str())
-        }).collect();
-    messages.sort();
-    println!("{:?}", messages.iter().take(10));
-    let guard_events = messages.iter().fold(Vec::<GuardEvent>::new(), |mut events, message| {
+fn lines_to_guard_events(input: &str) -> Result<Vec<GuardEvent>, Vec<ParseError>> {
+    let lines = parse::numbered_lines(input);
+    let parsed = parse::parse_lines(&lines, Message::from_str)?;
+    let mut messages: Vec<(usize, &str, Message)> =
+        lines.iter().zip(parsed).map(|((n, line), message)| (*n, *line, message)).collect();
+    messages.sort_by(|(_, _, a), (_, _, b)| a.cmp(b));
+    trace!("{:?}", messages.iter().take(10).map(|(_, _, m)| m).collect::<Vec<&Message>>());
+
+    let mut events = Vec::<GuardEvent>::new();
+    let mut errors = Vec::new();
+    for (n, line, message) in &messages {
+        // events are parsed from the message alone, so shift their columns past the timestamp
+        let offset = line.len() - message.message.len();
         let event = match events.last() {
             None => {
-                GuardEvent::parse(message).expect("Could not parse new event")
+                GuardEvent::parse(message)
             }
             Some(ev) => {
                 let guard = ev.record().guard.to_string();
-                GuardEvent::parse_for_guard(guard, message).expect("Could not parse new event")
+                GuardEvent::parse_for_guard(guard, message)
             }
         };
-        events.push(event);
-        events
-    });
-    guard_events
+        match event {
+            Ok(event) => {
+                if let Err(kind) = check_event_order(events.last(), &event) {
+                    errors.push(ParseError::new(kind, line).at_line(*n).at_column(offset));
+                }
+                events.push(event);
+            }
+            Err(e) => errors.push(ParseError::new(e.kind, line).at_line(*n).at_column(offset + e.column.unwrap_or(0))),
+        }
+    }
+    if errors.is_empty() { Ok(events) } else { Err(errors) }
 }
 
 #[cfg(test)]
@@ -291,32 +317,112 @@ mod example_tests {
 
     #[test]
     fn part_one_acceptance_test() {
-        assert_eq!(solve_part_one(&String::from(INPUT)), "240");
+        assert_eq!(solve_part_one(INPUT).unwrap(), "240");
+    }
+
+    #[test]
+    fn malformed_records_are_reported_with_their_line() {
+        assert_eq!(solve_part_one("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-31 00:05] falls asleep\n"),
+                   Err(SolveError::Parse(vec![ParseError::new(ParseErrorKind::BadTimestamp(String::from("1518-11-31 00:05")),
+                                                              "[1518-11-31 00:05] falls asleep").at_line(2).at_column(1)])));
+    }
+
+    #[test]
+    fn events_out_of_order_are_reported_with_their_line() {
+        assert_eq!(solve_part_one("[1518-11-01 00:25] wakes up\n[1518-11-01 00:00] Guard #10 begins shift\n"),
+                   Err(SolveError::Parse(vec![ParseError::new(
+                       ParseErrorKind::UnexpectedEventOrder(String::from("the guard wakes up without having fallen asleep")),
+                       "[1518-11-01 00:25] wakes up").at_line(1).at_column(19)])));
+        assert_eq!(solve_part_one("[1518-11-01 00:05] falls asleep\n[1518-11-01 00:06] dozes off\n"),
+                   Err(SolveError::Parse(vec![
+                       ParseError::new(
+                           ParseErrorKind::UnexpectedEventOrder(String::from("the first record must be a guard beginning their shift")),
+                           "[1518-11-01 00:05] falls asleep").at_line(1).at_column(19),
+                       ParseError::new(
+                           ParseErrorKind::MalformedLine("'falls asleep', 'wakes up' or 'Guard #10 begins shift'"),
+                           "[1518-11-01 00:06] dozes off").at_line(2).at_column(19),
+                   ])));
+    }
+
+    #[test]
+    fn waking_up_in_the_minute_of_falling_asleep_is_reported_with_its_line() {
+        assert_eq!(solve_part_one("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n[1518-11-01 00:05] wakes up\n"),
+                   Err(SolveError::Parse(vec![ParseError::new(
+                       ParseErrorKind::UnexpectedEventOrder(String::from("the guard wakes up no later than they fell asleep")),
+                       "[1518-11-01 00:05] wakes up").at_line(3).at_column(19)])));
+    }
+
+    #[test]
+    fn no_guards_should_produce_no_solution() {
+        assert_eq!(solve_part_one(""), Err(SolveError::NoSolution(String::from("No guard found!"))));
     }
 
     #[test]
     fn part_two_acceptance_test() {
-        assert_eq!(solve_part_two(&String::from(INPUT)), "4455");
+        assert_eq!(solve_part_two(INPUT).unwrap(), "4455");
     }
 
 }
 
-fn minute_most_asleep_for_guard(sleep_times: GuardSleepRanges) -> (String, u32) {
+fn minute_most_asleep_for_guard(sleep_times: GuardSleepRanges) -> Option<(String, u32)> {
     let mut guard_to_minute_most_asleep: Vec<(String, u32, u32)> = sleep_times.iter()
         .map(|(guard, times)| {
             let asleep = minute_most_asleep(times);
             (guard.to_string(), asleep.0, asleep.1)
     }).collect();
     guard_to_minute_most_asleep.sort_by_key(|k| { k.2 });
-    let (guard_most_asleep, minute, _) = guard_to_minute_most_asleep.iter().last().expect("No guard found!");
-    (guard_most_asleep.clone(), minute.clone())
+    guard_to_minute_most_asleep.iter().last().map(|(guard_most_asleep, minute, _)| (guard_most_asleep.clone(), *minute))
 }
 
 
-pub fn solve_part_two(input: &String) -> String {
-    let guard_events = lines_to_guard_events(input);
+fn solve_part_two(input: &str) -> Result<Answer, SolveError> {
+    let guard_events = lines_to_guard_events(input)?;
     let sleep_times = guard_sleep_times(&guard_events);
-    let (guard_id, most_frequent_minute) = minute_most_asleep_for_guard(sleep_times);
+    let (guard, most_frequent_minute) = minute_most_asleep_for_guard(sleep_times)
+        .ok_or_else(|| SolveError::NoSolution(String::from("No guard found!")))?;
+
+    Ok((guard_id(&guard).map_err(|kind| ParseError::new(kind, &guard))? * most_frequent_minute).into())
+}
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Repose Record"
+    }
+
+    fn parse(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(lines_to_guard_events(input)?.len())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part_two(input)
+    }
+}
 
-    (u32::from_str(guard_id.as_str()).unwrap() * most_frequent_minute).to_string()
+#[cfg(test)]
+mod validate_tests {
+    use super::*;
+
+    #[test]
+    fn events_are_counted() {
+        assert_eq!(Day04.parse("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n[1518-11-01 00:25] wakes up\n"),
+                   Ok(3));
+    }
+
+    #[test]
+    fn zero_length_sleep_is_reported() {
+        assert_eq!(Day04.parse("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n[1518-11-01 00:05] wakes up\n"),
+                   Err(SolveError::Parse(vec![ParseError::new(
+                       ParseErrorKind::UnexpectedEventOrder(String::from("the guard wakes up no later than they fell asleep")),
+                       "[1518-11-01 00:05] wakes up").at_line(3).at_column(19)])));
+    }
 }
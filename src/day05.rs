@@ -1,9 +1,12 @@
-pub fn solve_part_one(input: &String) -> String {
-    let letters = react_polymer(input);
+use parse::{self, ParseError, ParseErrorKind};
+use solver::{Answer, SolveError, Solver};
 
-    println!("{:?}", letters);
+fn solve_part_one(input: &str) -> Result<Answer, SolveError> {
+    let letters = react_polymer(parse_polymer(input)?);
 
-    letters.len().to_string()
+    trace!("{:?}", letters);
+
+    Ok(letters.len().into())
 }
 
 #[cfg(test)]
@@ -12,31 +15,86 @@ mod part_one_tests {
 
     #[test]
     fn two_letter_polymer_with_reaction_leaves_nothing() {
-        assert_eq!(solve_part_one(&String::from("Aa")), "0");
+        assert_eq!(solve_part_one("Aa").unwrap(), "0");
     }
 
     #[test]
     fn four_letter_polymer_with_reactions_leaves_nothing() {
-        assert_eq!(solve_part_one(&String::from("abBA")), "0");
+        assert_eq!(solve_part_one("abBA").unwrap(), "0");
+    }
+
+    #[test]
+    fn empty_and_single_unit_polymers_do_not_react() {
+        assert_eq!(solve_part_one("").unwrap(), "0");
+        assert_eq!(solve_part_one("a").unwrap(), "1");
     }
 
     #[test]
     fn example_polymer() {
-        assert_eq!(solve_part_one(&String::from("dabAcCaCBAcCcaDA")), "10");
+        assert_eq!(solve_part_one("dabAcCaCBAcCcaDA").unwrap(), "10");
     }
 
     #[test]
     fn four_letter_polymer_with_no_reactions_removes_nothing() {
-        assert_eq!(solve_part_one(&String::from("abAB")), "4");
-        assert_eq!(solve_part_one(&String::from("aabAAB")), "6");
+        assert_eq!(solve_part_one("abAB").unwrap(), "4");
+        assert_eq!(solve_part_one("aabAAB").unwrap(), "6");
+    }
+}
+
+/// Read the polymer's units, reporting every one that is not a letter, and any lines after the
+/// first.
+///
+fn parse_polymer(input: &str) -> Result<Vec<char>, Vec<ParseError>> {
+    let lines = parse::numbered_lines(input);
+    let mut errors = Vec::new();
+    for (n, line) in lines.iter().skip(1) {
+        errors.push(ParseError::new(ParseErrorKind::MalformedLine("the polymer on a single line"), line).at_line(*n));
+    }
+    let (n, polymer) = match lines.first() {
+        Some(&first) => first,
+        None => return Ok(Vec::new()),
+    };
+    for (column, unit) in polymer.char_indices().filter(|(_, c)| !c.is_ascii_alphabetic()) {
+        errors.push(ParseError::new(ParseErrorKind::UnexpectedCharacter(unit, "a letter"), polymer)
+            .at_line(n)
+            .at_column(column));
+    }
+    errors.sort_by_key(|e| (e.line, e.column));
+    if errors.is_empty() { Ok(polymer.chars().collect()) } else { Err(errors) }
+}
+
+#[cfg(test)]
+mod parse_polymer_tests {
+    use super::*;
+
+    #[test]
+    fn letters_are_units() {
+        assert_eq!(parse_polymer("\ndabA\n"), Ok(vec!['d', 'a', 'b', 'A']));
+    }
+
+    #[test]
+    fn every_unit_that_is_not_a_letter_is_reported() {
+        let errors = parse_polymer("ab1A-").unwrap_err();
+        assert_eq!(errors.iter().map(|e| (e.line, e.column)).collect::<Vec<_>>(),
+                   vec![(Some(1), Some(2)), (Some(1), Some(4))]);
+        assert_eq!(errors[1].kind, ParseErrorKind::UnexpectedCharacter('-', "a letter"));
+    }
+
+    #[test]
+    fn the_polymer_is_a_single_line() {
+        let errors = parse_polymer("abBA\n\ncd").unwrap_err();
+        assert_eq!(errors, vec![
+            ParseError::new(ParseErrorKind::MalformedLine("the polymer on a single line"), "cd").at_line(3),
+        ]);
     }
 }
 
 fn is_reaction(first: &char, second: &char) -> bool {
-    first != second && first.to_ascii_uppercase() == second.to_ascii_uppercase()
+    first != second && first.eq_ignore_ascii_case(second)
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod is_reaction_tests {
     use super::*;
 
@@ -62,19 +120,20 @@ mod is_reaction_tests {
 }
 
 
-pub fn solve_part_two(input: &String) -> String {
-    let after_initial_reaction = react_polymer(input).iter().map(|c| *c).collect::<String>();
+fn solve_part_two(input: &str) -> Result<Answer, SolveError> {
+    let after_initial_reaction = react_polymer(parse_polymer(input)?);
     let units: Vec<char> = "abcdefghijklmnopqrstuvwxyz".chars().collect();
 
     let with_units_removed = units.iter().map(|c| {
         let with_unit_removed =
-            after_initial_reaction
-                .replace(&c.to_string(), "")
-                .replace(&c.to_ascii_uppercase().to_string(), "");
-        react_polymer(&with_unit_removed).len()
+            after_initial_reaction.iter()
+                .filter(|u| !u.eq_ignore_ascii_case(c))
+                .cloned()
+                .collect();
+        react_polymer(with_unit_removed).len()
     });
 
-    with_units_removed.min().unwrap().to_string()
+    Ok(with_units_removed.min().unwrap_or(0).into())
 }
 
 #[cfg(test)]
@@ -83,13 +142,15 @@ mod part_two_tests {
 
     #[test]
     fn two_letter_polymer_with_reaction_leaves_nothing() {
-        assert_eq!(solve_part_two(&String::from("dabAcCaCBAcCcaDA")), "4");
+        assert_eq!(solve_part_two("dabAcCaCBAcCcaDA").unwrap(), "4");
     }
 }
 
-fn react_polymer(input: &String) -> Vec<char> {
-    let mut letters = input.trim().chars().collect::<Vec<char>>();
+fn react_polymer(mut letters: Vec<char>) -> Vec<char> {
     let mut ptr = 0;
+    if letters.len() < 2 {
+        return letters;
+    }
     loop {
         let first = letters[ptr];
         let second = letters[ptr + 1];
@@ -99,7 +160,7 @@ fn react_polymer(input: &String) -> Vec<char> {
         if is_reaction(&first, &second) {
             letters.remove(ptr);
             letters.remove(ptr);
-            if ptr != 0 { ptr -= 1 };
+            ptr = ptr.saturating_sub(1);
 //            println!("REACTION! {} <=> {}", first, second);
         } else {
             ptr += 1;
@@ -114,3 +175,27 @@ fn react_polymer(input: &String) -> Vec<char> {
     }
     letters
 }
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Alchemical Reduction"
+    }
+
+    fn parse(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(parse_polymer(input)?.len())
+    }
+
+    fn part_one(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Answer, SolveError> {
+        solve_part_two(input)
+    }
+}
@@ -0,0 +1,164 @@
+use parse::ParseError;
+
+/// Lines longer than this (in chars) are cut down to the part around the failing column.
+const MAX_EXCERPT: usize = 72;
+
+/// Render parse errors in the style of compiler diagnostics, with the file and line, the line
+/// itself, and a caret under the column where parsing failed.
+///
+/// ```text
+/// error: expected a claim like '#1 @ 1,3: 4x4'
+///  --> inputs/2018/day03.txt:2:9
+///   |
+/// 2 | #12 @ 3,x: 4x4
+///   |         ^
+/// ```
+///
+pub fn render(source: &str, errors: &[ParseError]) -> String {
+    let gutter = errors.iter()
+        .filter_map(|e| e.line)
+        .map(|line| line.to_string().len())
+        .max()
+        .unwrap_or(1);
+    let blank = " ".repeat(gutter);
+
+    let mut rendered = String::new();
+    for e in errors {
+        rendered.push_str(&format!("error: {}\n", e.kind));
+        let line = e.line.map_or(String::new(), |l| l.to_string());
+        match (e.line, e.column) {
+            (Some(l), Some(c)) => rendered.push_str(&format!("{}--> {}:{}:{}\n", blank, source, l, char_column(&e.text, c) + 1)),
+            (Some(l), None) => rendered.push_str(&format!("{}--> {}:{}\n", blank, source, l)),
+            (None, _) => rendered.push_str(&format!("{}--> {}\n", blank, source)),
+        }
+        rendered.push_str(&format!("{} |\n", blank));
+        let (text, caret) = excerpt(&e.text, e.column);
+        rendered.push_str(&format!("{:>width$} | {}\n", line, text, width = gutter));
+        if let Some(indent) = caret {
+            rendered.push_str(&format!("{} | {}^\n", blank, " ".repeat(indent)));
+        }
+        rendered.push('\n');
+    }
+    let count = errors.len();
+    rendered.push_str(&format!("{} error{} in {}\n", count, if count == 1 { "" } else { "s" }, source));
+    rendered
+}
+
+/// The part of `text` to show, and how many chars into it the caret for the byte offset `column`
+/// goes. Long lines are cut to `MAX_EXCERPT` chars around the column, marked with "...".
+///
+fn excerpt(text: &str, column: Option<usize>) -> (String, Option<usize>) {
+    let chars: Vec<char> = text.chars().collect();
+    let caret = column.map(|c| char_column(text, c));
+    if chars.len() <= MAX_EXCERPT {
+        return (text.to_string(), caret);
+    }
+    let start = caret.unwrap_or(0).saturating_sub(MAX_EXCERPT / 2).min(chars.len() - MAX_EXCERPT);
+    let end = start + MAX_EXCERPT;
+    let mut shown = if start > 0 { String::from("...") } else { String::new() };
+    shown.extend(&chars[start..end]);
+    if end < chars.len() {
+        shown.push_str("...");
+    }
+    let offset = if start > 0 { 3 } else { 0 };
+    (shown, caret.map(|c| c - start + offset))
+}
+
+/// How many chars into `text` the byte offset `column` is.
+fn char_column(text: &str, column: usize) -> usize {
+    text.get(..column).map_or(column, |prefix| prefix.chars().count())
+}
+
+#[cfg(test)]
+mod excerpt_tests {
+    use super::*;
+
+    #[test]
+    fn short_lines_are_shown_whole() {
+        assert_eq!(excerpt("dabAcC", Some(3)), (String::from("dabAcC"), Some(3)));
+        assert_eq!(excerpt("dabAcC", None), (String::from("dabAcC"), None));
+    }
+
+    #[test]
+    fn caret_counts_chars_not_bytes() {
+        assert_eq!(excerpt("é1", Some(2)), (String::from("é1"), Some(1)));
+    }
+
+    #[test]
+    fn long_lines_are_cut_around_the_column() {
+        let polymer = "a".repeat(200) + "1" + &"b".repeat(200);
+        let (shown, caret) = excerpt(&polymer, Some(200));
+        assert_eq!(shown, format!("...{}1{}...", "a".repeat(36), "b".repeat(35)));
+        assert_eq!(shown.chars().nth(caret.unwrap()), Some('1'));
+    }
+
+    #[test]
+    fn long_lines_are_cut_at_their_ends() {
+        let polymer = "1".to_string() + &"a".repeat(100);
+        assert_eq!(excerpt(&polymer, Some(0)), (format!("1{}...", "a".repeat(71)), Some(0)));
+        let (shown, caret) = excerpt(&polymer, Some(100));
+        assert_eq!(shown, format!("...{}", "a".repeat(72)));
+        assert_eq!(caret, Some(74));
+    }
+}
+
+#[cfg(test)]
+mod render_tests {
+    use super::*;
+    use parse::ParseErrorKind;
+
+    #[test]
+    fn caret_under_failing_column() {
+        let errors = vec![
+            ParseError::new(ParseErrorKind::MalformedLine("a claim like '#1 @ 1,3: 4x4'"), "#12 @ 3,x: 4x4").at_line(2).at_column(8),
+        ];
+        assert_eq!(render("day03.txt", &errors), "\
+error: expected a claim like '#1 @ 1,3: 4x4'
+ --> day03.txt:2:9
+  |
+2 | #12 @ 3,x: 4x4
+  |         ^
+
+1 error in day03.txt
+");
+    }
+
+    #[test]
+    fn column_counts_chars_not_bytes() {
+        let errors = vec![
+            ParseError::new(ParseErrorKind::UnexpectedCharacter('1', "a letter"), "aé1").at_line(1).at_column(3),
+        ];
+        assert_eq!(render("day05.txt", &errors), "\
+error: unexpected '1', expected a letter
+ --> day05.txt:1:3
+  |
+1 | aé1
+  |   ^
+
+1 error in day05.txt
+");
+    }
+
+    #[test]
+    fn every_error_is_rendered_with_a_shared_gutter() {
+        let errors = vec![
+            ParseError::new(ParseErrorKind::BadTimestamp(String::from("1518-11-31 00:05")), "[1518-11-31 00:05] falls asleep")
+                .at_line(9).at_column(1),
+            ParseError::new(ParseErrorKind::BadNumber(String::from("x")), "x").at_line(10),
+        ];
+        assert_eq!(render("<stdin>", &errors), "\
+error: bad timestamp '1518-11-31 00:05'
+  --> <stdin>:9:2
+   |
+ 9 | [1518-11-31 00:05] falls asleep
+   |  ^
+
+error: bad number 'x'
+  --> <stdin>:10
+   |
+10 | x
+
+2 errors in <stdin>
+");
+    }
+}
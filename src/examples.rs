@@ -0,0 +1,249 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use markdown;
+use solver::{Answer, Part, Solver};
+use verify::Outcome;
+
+/// Where example fixtures are kept, relative to the working directory. Unlike inputs they are the
+/// same for everyone, so they are meant to be checked in.
+///
+pub const DEFAULT_FIXTURES_DIR: &str = "fixtures";
+
+const HEADER_PREFIX: &str = "=== part ";
+const HEADER_SEPARATOR: &str = " expects ";
+
+/// A worked example from a puzzle description: an input and the answer it should give.
+///
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Example {
+    pub part: Part,
+    pub input: String,
+    pub expected: Answer,
+}
+
+/// Where the fixtures for a day are kept, as `<root>/2018/day05.txt`.
+///
+pub fn fixture_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(year.to_string()).join(format!("day{:02}.txt", day))
+}
+
+/// Find the worked examples on a puzzle page: each part's first `<pre><code>` block, or the one
+/// before it if it has none, with the last emphasized value in the part as the expected answer.
+///
+/// The site does not mark examples up as such, so this is a best guess that the saved fixtures can
+/// be corrected from.
+///
+pub fn extract(page: &str) -> Vec<Example> {
+    let mut examples = Vec::new();
+    let mut input: Option<String> = None;
+    for (article, part) in markdown::articles(page).iter().zip(&[Part::One, Part::Two]) {
+        if let Some(block) = between(article, "<pre><code>", "</code></pre>") {
+            input = Some(markdown::text_of(block));
+        }
+        let expected = last_emphasis(article);
+        if let (Some(input), Some(expected)) = (input.clone(), expected) {
+            examples.push(Example { part: *part, input, expected });
+        }
+    }
+    examples
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let from = text.find(start)? + start.len();
+    let to = text[from..].find(end)? + from;
+    Some(&text[from..to])
+}
+
+/// The last `<em>` in the article that holds something like an answer, i.e. its first word.
+fn last_emphasis(article: &str) -> Option<Answer> {
+    article.match_indices("<em>")
+        .filter_map(|(i, _)| between(&article[i..], "<em>", "</em>"))
+        .filter_map(|em| markdown::text_of(em).split_whitespace().next().map(str::to_string))
+        .filter(|word| word.chars().any(|c| c.is_ascii_alphanumeric()))
+        .last()
+        .map(|word| Answer::parse(&word))
+}
+
+#[cfg(test)]
+mod extract_tests {
+    use super::*;
+
+    const PAGE: &str = "<main><article class=\"day-desc\"><h2>--- Day 3: No Matter How You Slice It ---</h2>\
+        <p>For example, consider the following claims:</p>\n\
+        <pre><code>#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n</code></pre>\n\
+        <p>Visually, these claim the following areas:</p>\n<pre><code>........\n</code></pre>\n\
+        <p>The four square inches marked with <code>X</code> are claimed by <em>both 1 and 2</em>. \
+        In this example, <em>4</em> square inches of fabric are within two or more claims.</p></article>\n\
+        <p>Your puzzle answer was <code>110389</code>.</p>\
+        <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\
+        <p>For example, in the claims above, only claim <code><em>3</em></code> is intact after all claims are made.</p>\
+        </article></main>";
+
+    #[test]
+    fn examples_for_both_parts() {
+        let input = String::from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n");
+        assert_eq!(extract(PAGE), vec![
+            Example { part: Part::One, input: input.clone(), expected: Answer::from(4) },
+            Example { part: Part::Two, input, expected: Answer::from(3) },
+        ]);
+    }
+
+    #[test]
+    fn part_without_example_is_skipped() {
+        assert_eq!(extract("<article><h2>--- Day 1 ---</h2><p>No example, just <em>words</em>.</p></article>"), vec![]);
+    }
+}
+
+/// Read a day's fixtures, which look like:
+///
+/// ```text
+/// === part 1 expects 4
+/// #1 @ 1,3: 4x4
+/// #2 @ 3,1: 4x4
+/// === part 2 expects 3
+/// ...
+/// ```
+///
+/// A missing file has no examples.
+///
+pub fn load(path: &Path) -> Result<Vec<Example>, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => parse_fixtures(&contents).map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+pub fn save(path: &Path, examples: &[Example]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format_fixtures(examples))
+}
+
+fn parse_fixtures(contents: &str) -> Result<Vec<Example>, String> {
+    let mut examples: Vec<Example> = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        if let Some(header) = line.strip_prefix(HEADER_PREFIX) {
+            let mut header = header.splitn(2, HEADER_SEPARATOR);
+            let part = header.next().unwrap_or("").parse().map_err(|e| format!("line {}: {}", i + 1, e))?;
+            let expected = header.next().map(str::trim).filter(|e| !e.is_empty())
+                .ok_or_else(|| format!("line {}: Expected '=== part <n> expects <answer>'", i + 1))?;
+            examples.push(Example { part, input: String::new(), expected: Answer::parse(expected) });
+        } else {
+            let example = examples.last_mut().ok_or_else(|| format!("line {}: Expected '=== part <n> expects <answer>'", i + 1))?;
+            example.input.push_str(line);
+            example.input.push('\n');
+        }
+    }
+    for example in &mut examples {
+        let trimmed = example.input.trim_end().len();
+        example.input.truncate(trimmed);
+        example.input.push('\n');
+    }
+    Ok(examples)
+}
+
+fn format_fixtures(examples: &[Example]) -> String {
+    examples.iter()
+        .map(|e| format!("{}{}{}{}\n{}\n", HEADER_PREFIX, e.part, HEADER_SEPARATOR, e.expected, e.input.trim_end()))
+        .collect()
+}
+
+#[cfg(test)]
+mod fixture_tests {
+    use super::*;
+    use temp_dir::TempDir;
+
+    #[test]
+    fn save_then_load_round_trips() {
+        let dir = TempDir::new("fixtures");
+        let path = dir.join("2018").join("day05.txt");
+        let examples = vec![
+            Example { part: Part::One, input: String::from("dabAcCaCBAcCcaDA\n"), expected: Answer::from(10) },
+            Example { part: Part::Two, input: String::from("a\n\nb\n"), expected: Answer::from("fgij") },
+        ];
+        save(&path, &examples).unwrap();
+        assert_eq!(load(&path), Ok(examples));
+    }
+
+    #[test]
+    fn missing_fixtures_are_empty() {
+        assert_eq!(load(Path::new("no/such/fixtures.txt")), Ok(vec![]));
+    }
+
+    #[test]
+    fn bad_fixtures_should_produce_err_result() {
+        assert_eq!(parse_fixtures("abc\n"), Err(String::from("line 1: Expected '=== part <n> expects <answer>'")));
+        assert_eq!(parse_fixtures("=== part 3 expects 1\n"), Err(String::from("line 1: Unknown part '3', expected 1 or 2")));
+        assert_eq!(parse_fixtures("=== part 1 expects \n"), Err(String::from("line 1: Expected '=== part <n> expects <answer>'")));
+    }
+}
+
+/// The outcome of running one example.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub struct ExampleRun {
+    pub day: u8,
+    pub part: Part,
+    /// Which of the day's examples, counting from 1.
+    pub number: usize,
+    pub outcome: Outcome,
+}
+
+/// Run the solver against each of its examples.
+///
+pub fn run_examples(solver: &dyn Solver, examples: &[Example]) -> Vec<ExampleRun> {
+    examples.iter().enumerate().map(|(i, example)| {
+        let outcome = match solver.solve(example.part, &example.input) {
+            Err(e) => Outcome::Failed(e),
+            Ok(ref actual) if *actual == example.expected => Outcome::Pass,
+            Ok(actual) => Outcome::Mismatch { expected: example.expected.clone(), actual },
+        };
+        ExampleRun { day: solver.day(), part: example.part, number: i + 1, outcome }
+    }).collect()
+}
+
+/// A table of the runs, one row per example.
+///
+pub fn render_table(runs: &[ExampleRun]) -> String {
+    let mut table = format!("{:>3}  {:>4}  {:>7}  {:<6}  {}\n", "Day", "Part", "Example", "Result", "Detail");
+    for run in runs {
+        let (result, detail) = match run.outcome {
+            Outcome::Pass => ("PASS", String::new()),
+            Outcome::Mismatch { ref expected, ref actual } => ("FAIL", format!("expected '{}', got '{}'", expected, actual)),
+            Outcome::Failed(ref e) => ("FAIL", e.to_string()),
+            Outcome::NoInput | Outcome::Unrecorded(_) => ("SKIP", String::new()),
+        };
+        table.push_str(format!("{:>3}  {:>4}  {:>7}  {:<6}  {}", run.day, run.part, run.number, result, detail).trim_end());
+        table.push('\n');
+    }
+    let failures = runs.iter().filter(|r| r.outcome.is_failure()).count();
+    table.push_str(&format!("{} examples, {} passed, {} failed\n", runs.len(), runs.len() - failures, failures));
+    table
+}
+
+#[cfg(test)]
+mod run_examples_tests {
+    use super::*;
+    use day05::Day05;
+
+    #[test]
+    fn passes_and_mismatches() {
+        let examples = vec![
+            Example { part: Part::One, input: String::from("dabAcCaCBAcCcaDA\n"), expected: Answer::from(10) },
+            Example { part: Part::Two, input: String::from("dabAcCaCBAcCcaDA\n"), expected: Answer::from(5) },
+        ];
+        let runs = run_examples(&Day05, &examples);
+        assert_eq!(runs[0].outcome, Outcome::Pass);
+        assert_eq!(runs[1].outcome, Outcome::Mismatch { expected: Answer::from(5), actual: Answer::from(4) });
+        assert_eq!(render_table(&runs), "\
+Day  Part  Example  Result  Detail
+  5     1        1  PASS
+  5     2        2  FAIL    expected '5', got '4'
+2 examples, 1 passed, 1 failed
+");
+    }
+}
@@ -1,11 +1,220 @@
-extern crate reqwest;
-
+use chrono::Utc;
 use std::error::Error;
 
-pub fn input_for_day(sess: &str, day_input: &str) -> Result<String, Box<Error>> {
-    let client = reqwest::Client::new();
-    Ok(client.get(format!("https://adventofcode.com/2018/day/{}/input", day_input).as_str())
-        .header("Cookie", format!("session={}", sess))
-        .send()?
-        .text()?)
+use cache::InputCache;
+use http::Client;
+use markdown;
+use unlock;
+
+/// The site everything is fetched from and submitted to.
+///
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Markers of the pages the site serves in place of an input, e.g. when the session has expired.
+///
+const ERROR_PAGE_MARKERS: [&str; 4] = [
+    "Please log in",
+    "Please don't repeatedly request this endpoint before it unlocks",
+    "404 Not Found",
+    "<html",
+];
+
+/// The input for the day of the cache's year, from the cache if present, otherwise downloaded and then cached.
+///
+/// With `refresh`, the cache is skipped and the input is always downloaded again. The client's
+/// session is only required when a download is needed, which is refused before the puzzle unlocks.
+///
+pub fn input_for_day(client: &Client, day: u8, cache: &InputCache, refresh: bool) -> Result<String, Box<dyn Error>> {
+    if !refresh {
+        if let Some(input) = cache.get(day) {
+            return Ok(input);
+        }
+    }
+
+    unlock::check_unlocked(cache.year(), day, Utc::now())?;
+    if !client.has_session() {
+        return Err(From::from(format!("No session found in ADV_SESS or the profile, a session is needed to download the input for day {}", day)));
+    }
+    let input = client.get(&format!("/{}/day/{}/input", cache.year(), day))?;
+    if let Some(marker) = error_page_marker(&input) {
+        return Err(From::from(format!("Got an error page instead of the input for day {} ('{}')", day, marker)));
+    }
+    cache.put(day, &input)?;
+    Ok(input)
+}
+
+/// How the second part of a puzzle is headed, once it can be seen.
+///
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// The day's puzzle description as Markdown, from the cache if present, otherwise downloaded and
+/// then cached.
+///
+/// Part two is only shown once part one is solved, so a cached description without it is
+/// downloaded again, falling back to the cached one if that fails. No session is needed for part
+/// one.
+///
+pub fn description_for_day(client: &Client, day: u8, cache: &InputCache, refresh: bool) -> Result<String, Box<dyn Error>> {
+    let cached = if refresh { None } else { cache.get_description(day) };
+    if let Some(ref description) = cached {
+        if description.contains(PART_TWO_HEADING) {
+            return Ok(description.clone());
+        }
+    }
+
+    let description = puzzle_page(client, cache.year(), day).and_then(|page| {
+        let articles = markdown::articles(&page);
+        if articles.is_empty() {
+            return Err(From::from(format!("No puzzle description found on the page for day {}", day)));
+        }
+        Ok(articles.iter().map(|a| markdown::to_markdown(a)).collect::<Vec<String>>().join("\n"))
+    });
+    match (description, cached) {
+        (Ok(description), _) => {
+            cache.put_description(day, &description)?;
+            Ok(description)
+        }
+        (Err(e), Some(cached)) => {
+            info!("Could not check for part two, showing the cached description. {}", e);
+            Ok(cached)
+        }
+        (Err(e), None) => Err(e),
+    }
+}
+
+/// The HTML of the day's puzzle page, which shows part two too if the session has solved part one.
+///
+pub fn puzzle_page(client: &Client, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+    unlock::check_unlocked(year, day, Utc::now())?;
+    Ok(client.get(&format!("/{}/day/{}", year, day))?)
+}
+
+fn error_page_marker(body: &str) -> Option<&'static str> {
+    ERROR_PAGE_MARKERS.iter().find(|m| body.contains(*m)).cloned()
+}
+
+#[cfg(test)]
+mod input_for_day_tests {
+    use super::*;
+    use http::DEFAULT_TIMEOUT;
+    use mock_http::MockServer;
+    use std::time::Duration;
+    use temp_dir::TempDir;
+
+    fn temp_cache(name: &str) -> (TempDir, InputCache) {
+        let dir = TempDir::new(&format!("fetch-{}", name));
+        let cache = InputCache::new(dir.path(), 2018);
+        (dir, cache)
+    }
+
+    fn client(server: &MockServer) -> Client {
+        Client::new(&server.base_url, Some("abc123"), DEFAULT_TIMEOUT).unwrap().with_retries(1, Duration::from_millis(1))
+    }
+
+    #[test]
+    fn downloads_then_caches_the_input() {
+        let server = MockServer::start(vec![(200, "+1\n-2\n")]);
+        let (_dir, cache) = temp_cache("download");
+        assert_eq!(input_for_day(&client(&server), 1, &cache, false).unwrap(), "+1\n-2\n");
+        assert_eq!(server.request().request_line, "GET /2018/day/1/input HTTP/1.1");
+        assert_eq!(cache.get(1), Some(String::from("+1\n-2\n")));
+    }
+
+    #[test]
+    fn locked_puzzle_is_not_requested() {
+        let server = MockServer::start(vec![]);
+        let dir = TempDir::new("fetch-locked");
+        let cache = InputCache::new(dir.path(), 2100);
+        let err = input_for_day(&client(&server), 1, &cache, false).unwrap_err();
+        assert!(err.to_string().starts_with("Day 1 of 2100 unlocks in "));
+    }
+
+    #[test]
+    fn failed_download_is_not_cached() {
+        let server = MockServer::start(vec![(400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.")]);
+        let (_dir, cache) = temp_cache("expired");
+        let err = input_for_day(&client(&server), 1, &cache, false).unwrap_err();
+        assert!(err.to_string().ends_with("failed with status 400 (has the session expired?)"));
+        assert_eq!(cache.get(1), None);
+    }
+}
+
+#[cfg(test)]
+mod description_for_day_tests {
+    use super::*;
+    use http::DEFAULT_TIMEOUT;
+    use mock_http::MockServer;
+    use temp_dir::TempDir;
+
+    const PART_ONE: &str = "<main><article class=\"day-desc\"><h2>--- Day 1: Chronal Calibration ---</h2><p>Part one.</p></article></main>";
+    const BOTH_PARTS: &str = "<main><article class=\"day-desc\"><h2>--- Day 1: Chronal Calibration ---</h2><p>Part one.</p></article>\
+        <p>Your puzzle answer was <code>3</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Part two.</p></article></main>";
+
+    fn temp_cache(name: &str) -> (TempDir, InputCache) {
+        let dir = TempDir::new(&format!("description-{}", name));
+        let cache = InputCache::new(dir.path(), 2018);
+        (dir, cache)
+    }
+
+    fn client(server: &MockServer) -> Client {
+        Client::new(&server.base_url, None, DEFAULT_TIMEOUT).unwrap()
+    }
+
+    #[test]
+    fn downloads_every_part_as_markdown() {
+        let server = MockServer::start(vec![(200, BOTH_PARTS)]);
+        let (_dir, cache) = temp_cache("both");
+        let expected = "## --- Day 1: Chronal Calibration ---\n\nPart one.\n\n## --- Part Two ---\n\nPart two.\n";
+        assert_eq!(description_for_day(&client(&server), 1, &cache, false).unwrap(), expected);
+        assert_eq!(server.request().request_line, "GET /2018/day/1 HTTP/1.1");
+        assert_eq!(cache.get_description(1), Some(String::from(expected)));
+    }
+
+    #[test]
+    fn description_without_part_two_is_checked_again() {
+        let server = MockServer::start(vec![(200, PART_ONE), (200, BOTH_PARTS)]);
+        let (_dir, cache) = temp_cache("part-two");
+        assert!(!description_for_day(&client(&server), 1, &cache, false).unwrap().contains(PART_TWO_HEADING));
+        assert!(description_for_day(&client(&server), 1, &cache, false).unwrap().contains(PART_TWO_HEADING));
+        // both parts are cached now, so this does not need the server
+        assert!(description_for_day(&client(&server), 1, &cache, false).unwrap().contains(PART_TWO_HEADING));
+    }
+
+    #[test]
+    fn cached_description_is_kept_when_checking_fails() {
+        let server = MockServer::start(vec![(200, PART_ONE), (404, "Not Found")]);
+        let (_dir, cache) = temp_cache("fallback");
+        let first = description_for_day(&client(&server), 1, &cache, false).unwrap();
+        assert_eq!(description_for_day(&client(&server), 1, &cache, false).unwrap(), first);
+    }
+
+    #[test]
+    fn page_without_description_should_produce_err_result() {
+        let server = MockServer::start(vec![(200, "<p>Please log in</p>")]);
+        let (_dir, cache) = temp_cache("missing");
+        let err = description_for_day(&client(&server), 1, &cache, false).unwrap_err();
+        assert_eq!(err.to_string(), "No puzzle description found on the page for day 1");
+    }
+}
+
+#[cfg(test)]
+mod error_page_tests {
+    use super::*;
+
+    #[test]
+    fn puzzle_input_is_not_an_error_page() {
+        assert_eq!(error_page_marker("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n"), None);
+    }
+
+    #[test]
+    fn login_prompt_is_an_error_page() {
+        assert_eq!(error_page_marker("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+                   Some("Please log in"));
+    }
+
+    #[test]
+    fn premature_request_is_an_error_page() {
+        assert_eq!(error_page_marker("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"),
+                   Some("Please don't repeatedly request this endpoint before it unlocks"));
+    }
 }
@@ -0,0 +1,223 @@
+extern crate reqwest;
+
+use self::reqwest::header::{self, HeaderMap, HeaderValue};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+use timing;
+
+/// Sent with every request, so the site's owners can tell what is making them and who to contact.
+///
+pub const USER_AGENT: &str = concat!("advent2018/", env!("CARGO_PKG_VERSION"), " (+https://github.com/bbbates/advent2018)");
+
+/// How long a request may take when no `--timeout` is given.
+///
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+const DEFAULT_RETRIES: u32 = 3;
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Why a request to the site failed.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub enum HttpError {
+    /// The site answered, but not with success.
+    Status { url: String, status: u16 },
+    /// No answer came within the timeout.
+    Timeout { url: String, timeout: Duration },
+    /// The request could not be made, or its answer could not be read.
+    Transport { url: String, message: String },
+}
+
+impl HttpError {
+    /// Whether trying again might help: the site was unreachable, slow, or failing itself.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            HttpError::Status { status, .. } => *status >= 500,
+            HttpError::Timeout { .. } | HttpError::Transport { .. } => true,
+        }
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HttpError::Status { url, status } => {
+                let hint = match status {
+                    400 | 401 | 403 => " (has the session expired?)",
+                    404 => " (is the puzzle unlocked yet?)",
+                    _ => "",
+                };
+                write!(f, "Request to {} failed with status {}{}", url, status, hint)
+            }
+            HttpError::Timeout { url, timeout } =>
+                write!(f, "Request to {} timed out after {}", url, timing::format_duration(*timeout)),
+            HttpError::Transport { url, message } => write!(f, "Request to {} failed: {}", url, message),
+        }
+    }
+}
+
+impl Error for HttpError {}
+
+/// A client for the site at `base_url`, acting as the session's user.
+///
+/// GET requests are retried with exponential backoff when they fail for a transient reason. POST
+/// requests are sent only once, as a retried answer submission could count twice.
+///
+pub struct Client {
+    base_url: String,
+    sess: Option<String>,
+    timeout: Duration,
+    retries: u32,
+    backoff: Duration,
+    inner: reqwest::Client,
+}
+
+impl Client {
+    pub fn new(base_url: &str, sess: Option<&str>, timeout: Duration) -> Result<Client, HttpError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::USER_AGENT, HeaderValue::from_static(USER_AGENT));
+        let inner = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(timeout)
+            .build()
+            .map_err(|e| HttpError::Transport { url: base_url.to_string(), message: e.to_string() })?;
+        Ok(Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            sess: sess.map(str::to_string),
+            timeout,
+            retries: DEFAULT_RETRIES,
+            backoff: DEFAULT_BACKOFF,
+            inner,
+        })
+    }
+
+    /// Retry a failed GET up to `retries` times, waiting `backoff` before the first retry and
+    /// twice as long before each one after.
+    pub fn with_retries(self, retries: u32, backoff: Duration) -> Client {
+        Client { retries, backoff, ..self }
+    }
+
+    pub fn has_session(&self) -> bool {
+        self.sess.is_some()
+    }
+
+    /// The body of the page at `path`.
+    pub fn get(&self, path: &str) -> Result<String, HttpError> {
+        let url = self.url(path);
+        let mut attempt = 0;
+        loop {
+            match self.send(&url, self.inner.get(url.as_str())) {
+                Err(ref e) if e.is_transient() && attempt < self.retries => {
+                    let wait = self.backoff * 2u32.pow(attempt);
+                    info!("{}, retrying in {}", e, timing::format_duration(wait));
+                    thread::sleep(wait);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Post the form to `path`, returning the body of the response.
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, HttpError> {
+        let url = self.url(path);
+        self.send(&url, self.inner.post(url.as_str()).form(form))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn send(&self, url: &str, request: reqwest::RequestBuilder) -> Result<String, HttpError> {
+        let request = match self.sess {
+            Some(ref sess) => request.header(header::COOKIE, session_cookie(sess)),
+            None => request,
+        };
+        let mut response = request.send().map_err(|e| self.error(url, &e))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(HttpError::Status { url: url.to_string(), status: status.as_u16() });
+        }
+        response.text().map_err(|e| self.error(url, &e))
+    }
+
+    fn error(&self, url: &str, e: &reqwest::Error) -> HttpError {
+        // reqwest reports its own timeouts as WouldBlock on unix
+        let timed_out = e.get_ref()
+            .and_then(|inner| inner.downcast_ref::<io::Error>())
+            .is_some_and(|io_error| io_error.kind() == io::ErrorKind::TimedOut || io_error.kind() == io::ErrorKind::WouldBlock);
+        if timed_out {
+            HttpError::Timeout { url: url.to_string(), timeout: self.timeout }
+        } else {
+            HttpError::Transport { url: url.to_string(), message: e.to_string() }
+        }
+    }
+}
+
+/// The `Cookie` header value that authenticates requests as the session's user.
+///
+fn session_cookie(sess: &str) -> String {
+    format!("session={}", sess)
+}
+
+#[cfg(test)]
+mod client_tests {
+    use super::*;
+    use mock_http::MockServer;
+
+    fn client(server: &MockServer, retries: u32) -> Client {
+        Client::new(&server.base_url, Some("abc123"), Duration::from_millis(500)).unwrap()
+            .with_retries(retries, Duration::from_millis(1))
+    }
+
+    #[test]
+    fn get_sends_session_and_user_agent() {
+        let server = MockServer::start(vec![(200, "14\n")]);
+        assert_eq!(client(&server, 0).get("/2018/day/1/input"), Ok(String::from("14\n")));
+
+        let request = server.request();
+        assert_eq!(request.request_line, "GET /2018/day/1/input HTTP/1.1");
+        assert_eq!(request.header("Cookie"), Some("session=abc123"));
+        assert_eq!(request.header("User-Agent"), Some(USER_AGENT));
+    }
+
+    #[test]
+    fn failed_status_should_produce_err_result() {
+        let server = MockServer::start(vec![(400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.")]);
+        let err = client(&server, 3).get("/2018/day/1/input").unwrap_err();
+        assert_eq!(err, HttpError::Status { url: format!("{}/2018/day/1/input", server.base_url), status: 400 });
+        assert_eq!(err.to_string(), format!("Request to {}/2018/day/1/input failed with status 400 (has the session expired?)", server.base_url));
+    }
+
+    #[test]
+    fn server_errors_are_retried() {
+        let server = MockServer::start(vec![(500, "oops"), (503, "busy"), (200, "14\n")]);
+        assert_eq!(client(&server, 2).get("/2018/day/1/input"), Ok(String::from("14\n")));
+    }
+
+    #[test]
+    fn retries_are_bounded() {
+        let server = MockServer::start(vec![(502, "bad gateway"), (502, "bad gateway")]);
+        let err = client(&server, 1).get("/2018/day/1/input").unwrap_err();
+        assert_eq!(err, HttpError::Status { url: format!("{}/2018/day/1/input", server.base_url), status: 502 });
+    }
+
+    #[test]
+    fn posts_are_not_retried() {
+        let server = MockServer::start(vec![(500, "oops"), (200, "That's the right answer!")]);
+        let err = client(&server, 3).post_form("/2018/day/1/answer", &[("level", "1"), ("answer", "3")]).unwrap_err();
+        assert!(err.is_transient());
+        assert_eq!(server.request().body, "level=1&answer=3");
+    }
+
+    #[test]
+    fn slow_response_should_produce_timeout() {
+        let server = MockServer::silent();
+        let err = client(&server, 0).get("/2018/day/1/input").unwrap_err();
+        assert_eq!(err, HttpError::Timeout { url: format!("{}/2018/day/1/input", server.base_url), timeout: Duration::from_millis(500) });
+    }
+}
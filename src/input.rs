@@ -0,0 +1,102 @@
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use cache::InputCache;
+use fetch;
+use http::Client;
+
+/// Where a solver's puzzle input comes from.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub enum InputSource {
+    /// A file on disk.
+    File(PathBuf),
+    /// Standard input, given as `-` on the command line.
+    Stdin,
+    /// The input cache, falling back to downloading from the site.
+    Network { refresh: bool },
+}
+
+impl InputSource {
+    /// The source named by an `--input` argument.
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// A name for the source, for diagnostics.
+    pub fn name(&self, day: u8, cache: &InputCache) -> String {
+        match self {
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => String::from("<stdin>"),
+            InputSource::Network { .. } => cache.path_for(day).display().to_string(),
+        }
+    }
+
+    /// Read the input for the day. The client is only used when the input has to be downloaded.
+    pub fn read(&self, day: u8, client: &Client, cache: &InputCache) -> Result<String, Box<dyn Error>> {
+        match self {
+            InputSource::File(path) => fs::read_to_string(path)
+                .map_err(|e| From::from(format!("Could not read input file '{}': {}", path.display(), e))),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::Network { refresh } => fetch::input_for_day(client, day, cache, *refresh),
+        }
+    }
+}
+
+#[cfg(test)]
+mod input_source_tests {
+    use super::*;
+    use http::DEFAULT_TIMEOUT;
+    use temp_dir::TempDir;
+
+    fn no_session() -> Client {
+        Client::new(fetch::BASE_URL, None, DEFAULT_TIMEOUT).unwrap()
+    }
+
+    #[test]
+    fn dash_is_stdin() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(InputSource::from_arg("day05.txt"), InputSource::File(PathBuf::from("day05.txt")));
+    }
+
+    #[test]
+    fn names_for_diagnostics() {
+        let cache = InputCache::new("inputs", 2018);
+        assert_eq!(InputSource::from_arg("day05.txt").name(5, &cache), "day05.txt");
+        assert_eq!(InputSource::Stdin.name(5, &cache), "<stdin>");
+        assert_eq!(InputSource::Network { refresh: false }.name(5, &cache), "inputs/2018/day05.txt");
+    }
+
+    #[test]
+    fn read_from_file_needs_no_session() {
+        let dir = TempDir::new("input");
+        let path = dir.join("day05.txt");
+        fs::write(&path, "dabAcCaCBAcCcaDA\n").unwrap();
+        let input = InputSource::File(path).read(5, &no_session(), &InputCache::new("unused", 2018)).unwrap();
+        assert_eq!(input, "dabAcCaCBAcCcaDA\n");
+    }
+
+    #[test]
+    fn read_missing_file_should_produce_err_result() {
+        let err = InputSource::File(PathBuf::from("no/such/input.txt")).read(5, &no_session(), &InputCache::new("unused", 2018)).unwrap_err();
+        assert!(err.to_string().starts_with("Could not read input file 'no/such/input.txt'"));
+    }
+
+    #[test]
+    fn download_without_session_should_produce_err_result() {
+        let dir = TempDir::new("no-sess");
+        let cache = InputCache::new(dir.path(), 2018);
+        let err = InputSource::Network { refresh: false }.read(5, &no_session(), &cache).unwrap_err();
+        assert_eq!(err.to_string(), "No session found in ADV_SESS or the profile, a session is needed to download the input for day 5");
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use solver::{Answer, Part};
+use submit::Verdict;
+
+/// An answer submitted for a part, and the verdict it got.
+///
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Entry {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    pub verdict: Verdict,
+}
+
+/// Why the ledger refuses to let an answer be submitted.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub enum Rejection {
+    /// The part is already solved, with this answer.
+    Solved(Answer),
+    /// The answer has been submitted before and was wrong.
+    KnownWrong,
+    /// An answer at or below this one was already too high.
+    TooHigh(i64),
+    /// An answer at or above this one was already too low.
+    TooLow(i64),
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejection::Solved(answer) => write!(f, "This part is already solved, the answer was '{}'", answer),
+            Rejection::KnownWrong => write!(f, "This answer has already been submitted and was wrong"),
+            Rejection::TooHigh(bound) => write!(f, "The answer must be lower than {}, which was too high", bound),
+            Rejection::TooLow(bound) => write!(f, "The answer must be higher than {}, which was too low", bound),
+        }
+    }
+}
+
+/// A record of every answer submitted and its verdict, kept one tab separated entry per line.
+///
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Open the ledger at `path`, which starts empty if the file does not exist yet.
+    pub fn open<P: Into<PathBuf>>(path: P) -> io::Result<Ledger> {
+        let path = path.into();
+        let entries = match fs::read_to_string(&path) {
+            Ok(contents) => parse_entries(&contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))?,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Ledger { path, entries })
+    }
+
+    /// Record a verdict. Verdicts that say nothing about the answer itself are not kept.
+    pub fn record(&mut self, day: u8, part: Part, answer: &Answer, verdict: &Verdict) -> io::Result<()> {
+        if verdict_token(verdict).is_none() {
+            return Ok(());
+        }
+        let entry = Entry { day, part, answer: answer.clone(), verdict: verdict.clone() };
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", format_entry(&entry))?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// The correct answer for the part, once it has been solved.
+    pub fn solution(&self, day: u8, part: Part) -> Option<&Answer> {
+        self.for_part(day, part)
+            .find(|e| e.verdict == Verdict::Correct)
+            .map(|e| &e.answer)
+    }
+
+    /// Check an answer against everything known about the part before it is submitted.
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Result<(), Rejection> {
+        if let Some(solution) = self.solution(day, part) {
+            return Err(Rejection::Solved(solution.clone()));
+        }
+        if self.for_part(day, part).any(|e| e.answer == *answer) {
+            return Err(Rejection::KnownWrong);
+        }
+
+        if let Some(value) = answer.as_integer() {
+            let bounds = self.for_part(day, part)
+                .filter_map(|e| e.answer.as_integer().map(|n| (&e.verdict, n)));
+            for (verdict, bound) in bounds {
+                match verdict {
+                    Verdict::TooHigh if value >= bound => return Err(Rejection::TooHigh(bound)),
+                    Verdict::TooLow if value <= bound => return Err(Rejection::TooLow(bound)),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn for_part<'a>(&'a self, day: u8, part: Part) -> impl Iterator<Item = &'a Entry> + 'a {
+        self.entries.iter().filter(move |e| e.day == day && e.part == part)
+    }
+}
+
+fn verdict_token(verdict: &Verdict) -> Option<&'static str> {
+    match verdict {
+        Verdict::Correct => Some("correct"),
+        Verdict::Incorrect => Some("incorrect"),
+        Verdict::TooHigh => Some("too-high"),
+        Verdict::TooLow => Some("too-low"),
+        Verdict::AlreadySolved | Verdict::RateLimited(_) => None,
+    }
+}
+
+fn format_entry(entry: &Entry) -> String {
+    format!("{}\t{}\t{}\t{}", entry.day, entry.part, verdict_token(&entry.verdict).unwrap_or(""), entry.answer)
+}
+
+fn parse_entries(contents: &str) -> Result<Vec<Entry>, String> {
+    contents.lines().enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_entry(line).map_err(|e| format!("line {}: {}", i + 1, e)))
+        .collect()
+}
+
+fn parse_entry(line: &str) -> Result<Entry, String> {
+    let fields: Vec<&str> = line.splitn(4, '\t').collect();
+    if fields.len() != 4 {
+        return Err(format!("Expected day, part, verdict and answer in '{}'", line));
+    }
+    let verdict = match fields[2] {
+        "correct" => Verdict::Correct,
+        "incorrect" => Verdict::Incorrect,
+        "too-high" => Verdict::TooHigh,
+        "too-low" => Verdict::TooLow,
+        other => return Err(format!("Unknown verdict '{}'", other)),
+    };
+    Ok(Entry {
+        day: fields[0].parse().map_err(|_| format!("Bad day '{}'", fields[0]))?,
+        part: fields[1].parse()?,
+        answer: Answer::parse(fields[3]),
+        verdict,
+    })
+}
+
+#[cfg(test)]
+mod ledger_tests {
+    use super::*;
+    use temp_dir::TempDir;
+
+    fn temp_ledger(name: &str) -> (TempDir, Ledger) {
+        let dir = TempDir::new(&format!("ledger-{}", name));
+        let ledger = Ledger::open(dir.join("ledger.tsv")).unwrap();
+        (dir, ledger)
+    }
+
+    #[test]
+    fn missing_file_is_an_empty_ledger() {
+        let (_dir, ledger) = temp_ledger("missing");
+        assert!(ledger.entries.is_empty());
+    }
+
+    #[test]
+    fn recorded_entries_are_persisted() {
+        let (_dir, mut ledger) = temp_ledger("persisted");
+        ledger.record(4, Part::One, &Answer::from(240), &Verdict::TooLow).unwrap();
+        ledger.record(4, Part::One, &Answer::from(4455), &Verdict::Correct).unwrap();
+        ledger.record(4, Part::Two, &Answer::from(1), &Verdict::RateLimited(::std::time::Duration::from_secs(30))).unwrap();
+
+        let reopened = Ledger::open(ledger.path.clone()).unwrap();
+        assert_eq!(reopened.entries, ledger.entries);
+        assert_eq!(reopened.entries.len(), 2);
+        assert_eq!(reopened.solution(4, Part::One), Some(&Answer::from(4455)));
+        assert_eq!(reopened.solution(4, Part::Two), None);
+    }
+
+    #[test]
+    fn solved_part_rejects_further_answers() {
+        let (_dir, mut ledger) = temp_ledger("solved");
+        ledger.record(5, Part::One, &Answer::from(10), &Verdict::Correct).unwrap();
+        assert_eq!(ledger.check(5, Part::One, &Answer::from(11)), Err(Rejection::Solved(Answer::from(10))));
+        assert_eq!(ledger.check(5, Part::Two, &Answer::from(11)), Ok(()));
+    }
+
+    #[test]
+    fn known_wrong_answer_is_rejected() {
+        let (_dir, mut ledger) = temp_ledger("wrong");
+        ledger.record(2, Part::Two, &Answer::from("fgij"), &Verdict::Incorrect).unwrap();
+        assert_eq!(ledger.check(2, Part::Two, &Answer::from("fgij")), Err(Rejection::KnownWrong));
+        assert_eq!(ledger.check(2, Part::Two, &Answer::from("fghij")), Ok(()));
+    }
+
+    #[test]
+    fn answers_outside_bounds_are_rejected() {
+        let (_dir, mut ledger) = temp_ledger("bounds");
+        ledger.record(3, Part::One, &Answer::from(100), &Verdict::TooHigh).unwrap();
+        ledger.record(3, Part::One, &Answer::from(50), &Verdict::TooLow).unwrap();
+        assert_eq!(ledger.check(3, Part::One, &Answer::from(120)), Err(Rejection::TooHigh(100)));
+        assert_eq!(ledger.check(3, Part::One, &Answer::from(20)), Err(Rejection::TooLow(50)));
+        assert_eq!(ledger.check(3, Part::One, &Answer::from(75)), Ok(()));
+    }
+
+    #[test]
+    fn malformed_line_should_produce_err_result() {
+        assert_eq!(parse_entries("1\t1\tcorrect\t42\n1\t2\tmaybe\t7\n"), Err(String::from("line 2: Unknown verdict 'maybe'")));
+    }
+}
@@ -0,0 +1,51 @@
+//! Solvers for the Advent of Code 2018 puzzles, the toolkit for parsing puzzle input and the client
+//! for fetching it from the site. The `advent2018` binary is the command line over these.
+//!
+//! Each day implements `solver::Solver`, and `registry` lists them by year:
+//!
+//! ```
+//! use advent2018::registry;
+//!
+//! let day05 = registry::find(2018, 5).unwrap();
+//! assert_eq!(day05.part_one("dabAcCaCBAcCcaDA").unwrap(), "10");
+//! ```
+
+extern crate chrono;
+#[doc(hidden)]
+#[macro_use]
+pub extern crate lazy_static;
+extern crate regex;
+
+#[macro_use]
+pub mod logging;
+pub mod bench;
+pub mod cache;
+pub mod config;
+pub mod diagnostic;
+pub mod examples;
+pub mod fetch;
+pub mod http;
+pub mod input;
+pub mod ledger;
+pub mod markdown;
+#[macro_use]
+pub mod parse;
+pub mod solver;
+pub mod registry;
+pub mod report;
+pub mod run;
+pub mod scaffold;
+pub mod submit;
+pub mod timing;
+pub mod unlock;
+pub mod verify;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+
+#[cfg(test)]
+mod mock_http;
+#[cfg(test)]
+mod temp_dir;
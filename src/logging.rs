@@ -0,0 +1,102 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// How much is written to stderr; each level includes those before it.
+///
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Level {
+    Error,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// The level `steps` more verbose than this one, capped at `Trace`.
+    pub fn more_verbose(self, steps: usize) -> Level {
+        match self as usize + steps {
+            0 => Level::Error,
+            1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Level, String> {
+        match s {
+            "error" => Ok(Level::Error),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("Unknown verbosity '{}', expected error, info, debug or trace", s)),
+        }
+    }
+}
+
+static LEVEL: AtomicUsize = AtomicUsize::new(Level::Info as usize);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as usize, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as usize <= LEVEL.load(Ordering::Relaxed)
+}
+
+/// Write the message to stderr if the level is enabled, keeping stdout for answers only.
+///
+pub fn log(level: Level, message: fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("{}", message);
+    }
+}
+
+/// Log at the given level from within the library. They are not exported, the binary has its own
+/// that call `log`.
+///
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        $crate::logging::log($level, format_args!($($arg)*))
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => { log!($crate::logging::Level::Info, $($arg)*) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => { log!($crate::logging::Level::Debug, $($arg)*) };
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => { log!($crate::logging::Level::Trace, $($arg)*) };
+}
+
+#[cfg(test)]
+mod level_tests {
+    use super::*;
+
+    #[test]
+    fn more_verbose_steps_up_and_caps_at_trace() {
+        assert_eq!(Level::Info.more_verbose(0), Level::Info);
+        assert_eq!(Level::Info.more_verbose(1), Level::Debug);
+        assert_eq!(Level::Info.more_verbose(5), Level::Trace);
+        assert_eq!(Level::Error.more_verbose(1), Level::Info);
+    }
+
+    #[test]
+    fn levels_by_name() {
+        assert_eq!("debug".parse(), Ok(Level::Debug));
+        assert!("loud".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn levels_are_ordered_by_verbosity() {
+        assert!(Level::Error < Level::Info && Level::Info < Level::Debug && Level::Debug < Level::Trace);
+    }
+}
@@ -1,34 +1,403 @@
-extern crate core;
-extern crate regex;
-extern crate chrono;
-#[macro_use] extern crate lazy_static;
+extern crate advent2018;
 
 use std::env;
+use std::path::Path;
+use std::process;
 
-mod fetch;
-mod parse;
-mod day05;
+use advent2018::{bench, config, diagnostic, examples, fetch, http, logging, registry, report, run, scaffold, submit, unlock, verify};
+use advent2018::cache::InputCache;
+use advent2018::config::Config;
+use advent2018::http::Client;
+use advent2018::input::InputSource;
+use advent2018::ledger::Ledger;
+use advent2018::logging::Level;
+use advent2018::report::Format;
+use advent2018::solver::{SolveError, Solver};
+use cli::{Command, Options};
+
+macro_rules! error {
+    ($($arg:tt)*) => { logging::log(Level::Error, format_args!($($arg)*)) };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => { logging::log(Level::Info, format_args!($($arg)*)) };
+}
+
+mod cli;
 
 fn main() {
-    eprintln!("\n*** Advent 2018 w/ Rust Bootstrap and Solver Tool ***");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = cli::parse_args(&args).unwrap_or_else(|e| {
+        error!("{}\n{}", e, cli::USAGE);
+        process::exit(1);
+    });
+    let context = Context::new(&options);
+
+    info!("\n*** Advent {} w/ Rust Bootstrap and Solver Tool ***", context.year);
+
+    match options.command {
+        Command::Solve | Command::Submit => solve(&options, &context),
+        Command::Verify => verify(&options, &context),
+        Command::Bench => bench(&options, &context),
+        Command::All => all(&options, &context),
+        Command::Validate => validate(&options, &context),
+        Command::Read => read(&options, &context),
+        Command::Examples => run_examples(&options, &context),
+        Command::New => new_day(&options, &context),
+    }
+}
+
+/// The year, site client and input cache to work with, from the options and the config profile.
+///
+struct Context {
+    year: u16,
+    client: Client,
+    cache: InputCache,
+}
+
+impl Context {
+    /// Load the profile and settle the options against it, also setting the log level.
+    fn new(options: &Options) -> Context {
+        let path = config::config_path(env::var("ADV_CONFIG").ok(), env::var("XDG_CONFIG_HOME").ok(), env::var("HOME").ok());
+        let config = match path {
+            Some(ref path) => Config::load(path).unwrap_or_else(|e| {
+                error!("Error reading config file! {}", e);
+                process::exit(1);
+            }),
+            None => Config::default(),
+        };
+        let profile = config.profile(options.profile.as_deref()).unwrap_or_else(|e| {
+            error!("{}", e);
+            process::exit(1);
+        });
+        logging::set_level(options.level.or(profile.level).unwrap_or(Level::Info));
+
+        // a profile picked by name brings its own account, otherwise ADV_SESS can stand in for it
+        let env_sess = env::var("ADV_SESS").ok();
+        let sess = if options.profile.is_some() {
+            profile.session.clone().or(env_sess)
+        } else {
+            env_sess.or_else(|| profile.session.clone())
+        };
+        let timeout = options.timeout.or(profile.timeout).unwrap_or(http::DEFAULT_TIMEOUT);
+        let client = Client::new(fetch::BASE_URL, sess.as_deref(), timeout).unwrap_or_else(|e| {
+            error!("Error setting up the HTTP client! {}", e);
+            process::exit(1);
+        });
+        let year = options.year.or(profile.year).unwrap_or(cli::DEFAULT_YEAR);
+        Context { year, client, cache: InputCache::new(profile.cache_dir(), year) }
+    }
+}
+
+fn find_solver(year: u16, day_input: &str) -> Box<dyn Solver> {
+    match day_input.parse::<u8>().ok().and_then(|day| registry::find(year, day)) {
+        Some(solver) => solver,
+        None => {
+            error!("No solver for day '{}' of {}. Available days: {}", day_input, year, registry::available(year));
+            process::exit(1);
+        }
+    }
+}
+
+fn open_ledger(cache: &InputCache) -> Ledger {
+    Ledger::open(cache.ledger_path()).unwrap_or_else(|e| {
+        error!("Error reading answer ledger! {}", e);
+        process::exit(1);
+    })
+}
+
+fn read_input(options: &Options, context: &Context, solver: &dyn Solver) -> String {
+    options.input.read(solver.day(), &context.client, &context.cache).unwrap_or_else(|e| {
+        error!("Error reading input for problem! {}", e);
+        process::exit(1);
+    })
+}
+
+fn report_solve_error(e: &SolveError, options: &Options, context: &Context, solver: &dyn Solver) {
+    match e {
+        SolveError::Parse(ref errors) => error!("{}", diagnostic::render(&options.input.name(solver.day(), &context.cache), errors).trim_end()),
+        _ => error!("{}", e),
+    }
+}
+
+fn solve(options: &Options, context: &Context) {
+    let solver = find_solver(context.year, options.day.as_ref().expect("A day argument is required"));
+    let part = options.part;
+
+    info!("Solving Day {} ({}), Part {}", solver.day(), solver.title(), part);
+
+    if options.wait {
+        unlock::wait_for_unlock(context.year, solver.day());
+    }
+
+    let problem_input = read_input(options, context, solver.as_ref());
+
+    if options.format != Format::Text {
+        let result = run::run_part(solver.as_ref(), part, &problem_input);
+        print!("{}", report::render(options.format, context.year, std::slice::from_ref(&result)));
+        if result.answer.is_err() {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let answer = solver.solve(part, &problem_input).unwrap_or_else(|e| {
+        report_solve_error(&e, options, context, solver.as_ref());
+        process::exit(1);
+    });
+    println!("{}", answer);
+
+    // only submitting needs the ledger, a plain solve just skips the check against it
+    let ledger = if options.command == Command::Submit {
+        Some(open_ledger(&context.cache))
+    } else {
+        Ledger::open(context.cache.ledger_path())
+            .map_err(|e| info!("Not checking the answer against the answer ledger, it could not be read! {}", e))
+            .ok()
+    };
+    if let Some(solution) = ledger.as_ref().and_then(|l| l.solution(solver.day(), part)) {
+        if *solution != answer {
+            error!("Answer '{}' does not match the recorded solution '{}'", answer, solution);
+        }
+    }
+
+    if let (Command::Submit, Some(mut ledger)) = (&options.command, ledger) {
+        if let Err(rejection) = ledger.check(solver.day(), part, &answer) {
+            error!("Not submitting: {}", rejection);
+            process::exit(1);
+        }
+        if !context.client.has_session() {
+            error!("No session found in ADV_SESS or the profile, a session is needed to submit an answer");
+            process::exit(1);
+        }
+        info!("Submitting answer '{}' for Day {}, Part {}", answer, solver.day(), part);
+        match submit::submit_answer(&context.client, context.year, solver.day(), part, &answer) {
+            Ok(verdict) => {
+                println!("{}", verdict);
+                if let Err(e) = ledger.record(solver.day(), part, &answer, &verdict) {
+                    error!("Error recording verdict in the answer ledger! {}", e);
+                }
+                if !verdict.is_solved() {
+                    process::exit(1);
+                }
+            }
+            Err(e) => {
+                error!("Error submitting answer! {}", e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn verify(options: &Options, context: &Context) {
+    let solvers = match options.day {
+        Some(ref day_input) => vec![find_solver(context.year, day_input)],
+        None => registry::all(context.year),
+    };
+    let ledger = open_ledger(&context.cache);
+
+    let checks = verify::verify_all(&solvers, &context.cache, &ledger);
+    print!("{}", verify::render_table(&checks));
 
-    let default_part = String::from("1");
+    if checks.iter().any(|c| c.outcome.is_failure()) {
+        process::exit(1);
+    }
+}
 
-    let sess = env::var("ADV_SESS").expect("No ADV_SESS var found! Exiting...\n");
-    let args: Vec<String> = env::args().collect();
-    let day_input = args.get(1).expect("A day argument is required");
-    let day_part_input = args.get(2).unwrap_or(&default_part);
+fn bench(options: &Options, context: &Context) {
+    let solvers = match options.day {
+        Some(ref day_input) => vec![find_solver(context.year, day_input)],
+        None => registry::all(context.year),
+    };
+    if solvers.len() > 1 && !matches!(options.input, InputSource::Network { .. }) {
+        error!("--input can only be given when benchmarking a single day");
+        process::exit(1);
+    }
+    let baseline_path = context.cache.bench_baseline_path();
+    let baseline = bench::load_baseline(&baseline_path).unwrap_or_else(|e| {
+        error!("Error reading bench baseline! {}", e);
+        process::exit(1);
+    });
 
-    eprintln!("Solving Day {}, Part {}", day_input, day_part_input);
+    let mut measurements = Vec::new();
+    for solver in solvers {
+        info!("Benchmarking Day {} ({}), {} runs", solver.day(), solver.title(), options.runs);
+        let result = options.input.read(solver.day(), &context.client, &context.cache)
+            .and_then(|input| Ok(bench::bench_solver(solver.as_ref(), &input, options.runs)?));
+        match result {
+            Ok(mut m) => measurements.append(&mut m),
+            Err(e) => error!("Skipping Day {}: {}", solver.day(), e),
+        }
+    }
+    print!("{}", bench::render_table(&measurements, &baseline));
 
-    let problem_input = fetch::input_for_day(&sess, day_input).expect("Error fetching input for problem!");
+    if options.save_baseline || baseline.is_empty() {
+        if let Err(e) = bench::save_baseline(&baseline_path, &measurements) {
+            error!("Error saving bench baseline! {}", e);
+            process::exit(1);
+        }
+        info!("Saved baseline to {}", baseline_path.display());
+    }
+}
 
+fn all(options: &Options, context: &Context) {
+    if !matches!(options.input, InputSource::Network { .. }) {
+        error!("--input can not be given when running every day");
+        process::exit(1);
+    }
+
+    let results = run::run_all(&registry::all(context.year), |day| options.input.read(day, &context.client, &context.cache));
+    print!("{}", report::render(options.format, context.year, &results));
+
+    if results.iter().any(|r| r.answer.is_err()) {
+        process::exit(1);
+    }
+}
 
+fn validate(options: &Options, context: &Context) {
+    let solver = find_solver(context.year, options.day.as_ref().expect("A day argument is required"));
 
-    if day_part_input == "1" {
-        println!("{}", day05::solve_part_one(&problem_input));
+    info!("Validating input for Day {} ({})", solver.day(), solver.title());
+
+    let problem_input = read_input(options, context, solver.as_ref());
+
+    match solver.parse(&problem_input) {
+        Ok(records) => println!("{} is valid, {} record{}", options.input.name(solver.day(), &context.cache),
+                                records, if records == 1 { "" } else { "s" }),
+        Err(e) => {
+            report_solve_error(&e, options, context, solver.as_ref());
+            process::exit(1);
+        }
+    }
+}
+
+fn read(options: &Options, context: &Context) {
+    let day = parse_day(options.day.as_ref().expect("A day argument is required"));
+    let refresh = matches!(options.input, InputSource::Network { refresh: true });
+
+    match fetch::description_for_day(&context.client, day, &context.cache, refresh) {
+        Ok(description) => print!("{}", description),
+        Err(e) => {
+            error!("Error fetching puzzle description! {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_day(day_input: &str) -> u8 {
+    day_input.parse::<u8>().ok().filter(|d| (1..=25).contains(d)).unwrap_or_else(|| {
+        error!("No puzzle for day '{}', days run from 1 to 25", day_input);
+        process::exit(1);
+    })
+}
+
+fn run_examples(options: &Options, context: &Context) {
+    let solvers = match options.day {
+        Some(ref day_input) => vec![find_solver(context.year, day_input)],
+        None => registry::all(context.year),
+    };
+    let root = Path::new(examples::DEFAULT_FIXTURES_DIR);
+
+    if options.extract {
+        extract_examples(options, context, solvers[0].as_ref(), root);
+        return;
+    }
+
+    let mut runs = Vec::new();
+    for solver in solvers {
+        match examples::load(&examples::fixture_path(root, context.year, solver.day())) {
+            Ok(ref e) if e.is_empty() => info!("No examples saved for Day {}, use --extract to save them", solver.day()),
+            Ok(e) => runs.append(&mut examples::run_examples(solver.as_ref(), &e)),
+            Err(e) => {
+                error!("Error reading examples! {}", e);
+                process::exit(1);
+            }
+        }
+    }
+    print!("{}", examples::render_table(&runs));
+
+    if runs.iter().any(|r| r.outcome.is_failure()) {
+        process::exit(1);
+    }
+}
+
+fn extract_examples(options: &Options, context: &Context, solver: &dyn Solver, root: &Path) {
+    let path = examples::fixture_path(root, context.year, solver.day());
+    if path.exists() && !matches!(options.input, InputSource::Network { refresh: true }) {
+        error!("{} already exists, use --refresh to replace it", path.display());
+        process::exit(1);
+    }
+    let page = fetch::puzzle_page(&context.client, context.year, solver.day()).unwrap_or_else(|e| {
+        error!("Error fetching puzzle page! {}", e);
+        process::exit(1);
+    });
+    let found = examples::extract(&page);
+    if found.is_empty() {
+        error!("No examples found in the description of Day {}", solver.day());
+        process::exit(1);
+    }
+    examples::save(&path, &found).unwrap_or_else(|e| {
+        error!("Error saving examples! {}", e);
+        process::exit(1);
+    });
+    println!("Saved {} example(s) to {}, check them against the description", found.len(), path.display());
+}
+
+fn new_day(options: &Options, context: &Context) {
+    let day = parse_day(options.day.as_ref().expect("A day argument is required"));
+    if registry::find(context.year, day).is_some() {
+        error!("Day {} of {} is already registered", day, context.year);
+        process::exit(1);
+    }
+
+    let page = if options.download {
+        Some(fetch::puzzle_page(&context.client, context.year, day).unwrap_or_else(|e| {
+            error!("Error fetching puzzle page! {}", e);
+            process::exit(1);
+        }))
     } else {
-        println!("{}", day05::solve_part_two(&problem_input));
+        None
+    };
+    let title = page.as_ref().and_then(|p| scaffold::title(p)).unwrap_or_else(|| String::from(scaffold::PLACEHOLDER_TITLE));
+
+    let module_path = scaffold::create_day(Path::new("src"), context.year, day, &title).unwrap_or_else(|e| {
+        error!("Error generating day {}! {}", day, e);
+        process::exit(1);
+    });
+    println!("Generated {} for Day {} ({}) and registered it", module_path.display(), day, title);
+
+    let path = examples::fixture_path(Path::new(examples::DEFAULT_FIXTURES_DIR), context.year, day);
+    if let Some(ref page) = page {
+        let found = examples::extract(page);
+        if found.is_empty() {
+            info!("No examples found in the description of Day {}", day);
+        } else if path.exists() {
+            info!("Keeping the examples already saved in {}", path.display());
+        } else if let Err(e) = examples::save(&path, &found) {
+            error!("Error saving examples! {}", e);
+            process::exit(1);
+        } else {
+            println!("Saved {} example(s) to {}", found.len(), path.display());
+        }
     }
 
+    // the examples test expects fixtures for every day, unless it is told they are still to come
+    if !path.exists() {
+        if let Err(e) = scaffold::await_fixtures(Path::new(scaffold::EXAMPLES_TEST), context.year, day) {
+            error!("Error listing Day {} as awaiting fixtures! {}", day, e);
+            error!("Add ({}, {}) to AWAITING_FIXTURES in {} until examples are saved to {}",
+                   context.year, day, scaffold::EXAMPLES_TEST, path.display());
+            process::exit(1);
+        }
+        println!("Listed Day {} in AWAITING_FIXTURES in {} until examples are saved to {}",
+                 day, scaffold::EXAMPLES_TEST, path.display());
+    }
+
+    if page.is_some() {
+        if let Err(e) = fetch::input_for_day(&context.client, day, &context.cache, false) {
+            error!("Error downloading input! {}", e);
+            process::exit(1);
+        }
+        println!("Downloaded the input to {}", context.cache.path_for(day).display());
+    }
 }
@@ -0,0 +1,265 @@
+use fetch::BASE_URL;
+
+/// The `<article>` elements of a puzzle page, one for each part that can be seen.
+///
+pub fn articles(page: &str) -> Vec<&str> {
+    let mut articles = Vec::new();
+    let mut rest = page;
+    while let Some(start) = rest.find("<article") {
+        let end = rest[start..].find("</article>").map_or(rest.len(), |i| start + i + "</article>".len());
+        articles.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    articles
+}
+
+#[cfg(test)]
+mod articles_tests {
+    use super::*;
+
+    #[test]
+    fn each_article_on_the_page() {
+        let page = "<main><article class=\"day-desc\"><h2>One</h2></article><p>Answer</p><article class=\"day-desc\"><h2>Two</h2></article></main>";
+        assert_eq!(articles(page), vec!["<article class=\"day-desc\"><h2>One</h2></article>",
+                                        "<article class=\"day-desc\"><h2>Two</h2></article>"]);
+        assert!(articles("<p>Please log in</p>").is_empty());
+    }
+}
+
+/// Convert the HTML of a puzzle description to Markdown, which also reads well as plain text.
+/// Only the markup the site uses in descriptions is understood; other tags are dropped.
+///
+pub fn to_markdown(html: &str) -> String {
+    let mut md = Markdown::default();
+    let mut rest = html;
+    while !rest.is_empty() {
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(rest.len(), |i| i + 3);
+            rest = &rest[end..];
+        } else if rest.starts_with('<') {
+            let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+            md.tag(&rest[..end]);
+            rest = &rest[end..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            md.text(&decode_entities(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+    md.finish()
+}
+
+#[derive(Default)]
+struct Markdown {
+    out: String,
+    in_pre: bool,
+    in_code: bool,
+    list_depth: usize,
+    links: Vec<String>,
+}
+
+impl Markdown {
+    fn tag(&mut self, tag: &str) {
+        let inner = tag.trim_start_matches('<').trim_end_matches('>');
+        let closing = inner.starts_with('/');
+        let name = inner.trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+
+        match (name.as_str(), closing) {
+            ("h2", false) => {
+                self.block();
+                self.out.push_str("## ");
+            }
+            ("p", false) => self.block(),
+            ("h2", true) | ("p", true) => self.end_block(),
+            ("pre", false) => {
+                self.block();
+                self.out.push_str("```\n");
+                self.in_pre = true;
+            }
+            ("pre", true) => {
+                self.new_line();
+                self.out.push_str("```\n\n");
+                self.in_pre = false;
+            }
+            ("code", _) if !self.in_pre => {
+                self.out.push('`');
+                self.in_code = !closing;
+            }
+            ("em", _) if !self.in_pre && !self.in_code => self.out.push('*'),
+            ("ul", false) => {
+                if self.list_depth == 0 {
+                    self.block();
+                }
+                self.list_depth += 1;
+            }
+            ("ul", true) => {
+                self.list_depth = self.list_depth.saturating_sub(1);
+                if self.list_depth == 0 {
+                    self.end_block();
+                }
+            }
+            ("li", false) => {
+                self.new_line();
+                self.out.push_str(&"  ".repeat(self.list_depth.saturating_sub(1)));
+                self.out.push_str("- ");
+            }
+            ("a", false) => {
+                self.out.push('[');
+                self.links.push(attribute(inner, "href").unwrap_or_default());
+            }
+            ("a", true) => {
+                let href = self.links.pop().unwrap_or_default();
+                let href = if href.starts_with('/') { format!("{}{}", BASE_URL, href) } else { href };
+                self.out.push_str(&format!("]({})", href));
+            }
+            ("br", _) => self.out.push('\n'),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.in_pre {
+            self.out.push_str(text);
+            return;
+        }
+        for c in text.chars() {
+            if !c.is_whitespace() {
+                self.out.push(c);
+            } else if !self.out.is_empty() && !self.out.ends_with(char::is_whitespace) && !self.out.ends_with("- ") {
+                self.out.push(' ');
+            }
+        }
+    }
+
+    /// Start a block on a line of its own, after a blank line.
+    fn block(&mut self) {
+        self.trim_end();
+        if !self.out.is_empty() {
+            self.out.push_str("\n\n");
+        }
+    }
+
+    fn end_block(&mut self) {
+        self.trim_end();
+        self.out.push_str("\n\n");
+    }
+
+    fn new_line(&mut self) {
+        self.out.truncate(self.out.trim_end_matches(' ').len());
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    fn trim_end(&mut self) {
+        self.out.truncate(self.out.trim_end().len());
+    }
+
+    fn finish(mut self) -> String {
+        self.trim_end();
+        self.out.push('\n');
+        self.out
+    }
+}
+
+/// The text of an HTML fragment, with its tags dropped and its entities decoded.
+///
+pub fn text_of(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = rest[start..].find('>').map_or("", |end| &rest[start + end + 1..]);
+    }
+    text.push_str(rest);
+    decode_entities(&text)
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(decode_entities(&tag[start..end]))
+}
+
+/// Replace the character entities that appear in puzzle descriptions.
+///
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').filter(|end| *end <= 10).map(|end| &rest[1..end]);
+        let c = entity.and_then(|e| match e {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ if e.starts_with("#x") => u32::from_str_radix(&e[2..], 16).ok().and_then(std::char::from_u32),
+            _ if e.starts_with('#') => e[1..].parse().ok().and_then(std::char::from_u32),
+            _ => None,
+        });
+        match (c, entity) {
+            (Some(c), Some(e)) => {
+                decoded.push(c);
+                rest = &rest[e.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod text_of_tests {
+    use super::*;
+
+    #[test]
+    fn tags_dropped_and_entities_decoded() {
+        assert_eq!(text_of("<code>&lt;<em>x</em>&gt;</code> &amp; y\n"), "<x> & y\n");
+    }
+}
+
+#[cfg(test)]
+mod to_markdown_tests {
+    use super::*;
+
+    #[test]
+    fn headings_paragraphs_and_emphasis() {
+        assert_eq!(to_markdown("<article class=\"day-desc\"><h2>--- Day 5: Alchemical Reduction ---</h2><p>You've managed to sneak in to the prototype suit manufacturing lab.\nThe polymer is formed by smaller <em>units</em>.</p></article>"),
+                   "## --- Day 5: Alchemical Reduction ---\n\nYou've managed to sneak in to the prototype suit manufacturing lab. The polymer is formed by smaller *units*.\n");
+    }
+
+    #[test]
+    fn inline_code_and_code_blocks() {
+        assert_eq!(to_markdown("<p>In <code>aA</code>, <code><em>a</em></code> and <code>A</code> react:</p>\n<pre><code>dabAcCaCBAcCcaDA  The first 'cC' is removed.\ndabAaCBAcCcaDA    This creates 'Aa', which is removed.\n</code></pre><p>After</p>"),
+                   "In `aA`, `a` and `A` react:\n\n```\ndabAcCaCBAcCcaDA  The first 'cC' is removed.\ndabAaCBAcCcaDA    This creates 'Aa', which is removed.\n```\n\nAfter\n");
+    }
+
+    #[test]
+    fn lists_and_links() {
+        assert_eq!(to_markdown("<p>For example:</p>\n<ul>\n<li><code>+1, -1</code> first reaches <code>0</code> twice.</li>\n<li>Nested:<ul><li>inner</li></ul></li>\n</ul>\n<p>Get your <a href=\"/2018/day/1/input\" target=\"_blank\">puzzle input</a>.</p>"),
+                   "For example:\n\n- `+1, -1` first reaches `0` twice.\n- Nested:\n  - inner\n\nGet your [puzzle input](https://adventofcode.com/2018/day/1/input).\n");
+    }
+
+    #[test]
+    fn entities_are_decoded() {
+        assert_eq!(to_markdown("<p>&lt;x=1&gt; &amp; &quot;y&quot; &#39;z&#39; &#x2605; &unknown; AT&amp;T</p>"),
+                   "<x=1> & \"y\" 'z' \u{2605} &unknown; AT&T\n");
+    }
+
+    #[test]
+    fn unknown_tags_and_comments_are_dropped() {
+        assert_eq!(to_markdown("<p><span title=\"Easter egg\">Hidden</span> <!-- <b>note</b> -->text</p>"), "Hidden text\n");
+    }
+}
@@ -0,0 +1,98 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+
+/// A request received by a `MockServer`.
+///
+#[derive(Debug)]
+pub struct Request {
+    pub request_line: String,
+    pub headers: Vec<String>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let prefix = format!("{}:", name.to_lowercase());
+        self.headers.iter()
+            .find(|h| h.to_lowercase().starts_with(&prefix))
+            .map(|h| h[prefix.len()..].trim())
+    }
+}
+
+/// A stand-in HTTP server on a local port, answering each connection with the next canned response.
+///
+pub struct MockServer {
+    pub base_url: String,
+    requests: Receiver<Request>,
+}
+
+impl MockServer {
+    /// Serve one `(status, body)` response per connection, in order.
+    pub fn start(responses: Vec<(u16, &str)>) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let responses: Vec<(u16, String)> = responses.into_iter().map(|(s, b)| (s, b.to_string())).collect();
+        let (sender, requests) = channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let request = read_request(&mut BufReader::new(stream.try_clone().unwrap()));
+                write!(stream, "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nContent-Type: text/html\r\nConnection: close\r\n\r\n{}",
+                       status, body.len(), body).unwrap();
+                stream.flush().unwrap();
+                if sender.send(request).is_err() {
+                    break;
+                }
+            }
+        });
+
+        MockServer { base_url, requests }
+    }
+
+    /// Accept connections but never answer them, for timeouts.
+    pub fn silent() -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (_, requests) = channel();
+
+        thread::spawn(move || {
+            let mut open = Vec::new();
+            for stream in listener.incoming() {
+                open.push(stream);
+            }
+        });
+
+        MockServer { base_url, requests }
+    }
+
+    /// The next request the server received.
+    pub fn request(&self) -> Request {
+        self.requests.recv().unwrap()
+    }
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> Request {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end().to_string();
+        if line.is_empty() {
+            break;
+        }
+        headers.push(line);
+    }
+
+    let mut request = Request { request_line: request_line.trim_end().to_string(), headers, body: String::new() };
+    let length: usize = request.header("Content-Length").and_then(|l| l.parse().ok()).unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+    request.body = String::from_utf8(body).unwrap();
+    request
+}
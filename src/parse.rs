@@ -1,5 +1,535 @@
+extern crate regex_automata;
+
+use self::regex_automata::{dense, DenseDFA, DFA};
+use chrono;
+use regex::{self, Captures, Regex};
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+use std::num::{ParseFloatError, ParseIntError};
+use std::str::FromStr;
+
 /// Split an Advent of Code input into lines, filtering the empty ones.
 ///
-pub fn split_input_lines(input: &String) -> Vec<&str> {
+pub fn split_input_lines(input: &str) -> Vec<&str> {
     input.split("\n").map(str::trim).filter(|s| !s.is_empty()).collect()
 }
+
+/// Split an Advent of Code input into its non-empty lines, each with its line number (from 1).
+///
+pub fn numbered_lines(input: &str) -> Vec<(usize, &str)> {
+    input.split("\n")
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(i, s)| (i + 1, s))
+        .collect()
+}
+
+/// Parse each numbered line with `parse`, collecting every line that fails rather than stopping
+/// at the first.
+///
+pub fn parse_lines<T, F>(lines: &[(usize, &str)], parse: F) -> Result<Vec<T>, Vec<ParseError>>
+    where F: Fn(&str) -> Result<T, ParseError> {
+    let mut parsed = Vec::with_capacity(lines.len());
+    let mut errors = Vec::new();
+    for (n, line) in lines {
+        match parse(line) {
+            Ok(value) => parsed.push(value),
+            Err(e) => errors.push(e.at_line(*n)),
+        }
+    }
+    if errors.is_empty() { Ok(parsed) } else { Err(errors) }
+}
+
+/// Parse each non-empty line of the input as a `T`, collecting every line that fails.
+///
+pub fn lines<T>(input: &str) -> Result<Vec<T>, Vec<ParseError>>
+    where T: FromStr, T::Err: IntoParseError {
+    parse_lines(&numbered_lines(input), |line| T::from_str(line).map_err(|e| e.into_parse_error(line, line, 0)))
+}
+
+#[cfg(test)]
+mod lines_tests {
+    use super::*;
+
+    #[test]
+    fn signed_numbers_one_per_line() {
+        assert_eq!(lines::<i32>("+1\n-2\n\n 3 \n"), Ok(vec![1, -2, 3]));
+    }
+
+    #[test]
+    fn every_bad_line_is_reported() {
+        assert_eq!(lines::<u8>("1\n256\nx"), Err(vec![
+            ParseError::new(ParseErrorKind::BadNumber(String::from("256")), "256").at_line(2).at_column(0),
+            ParseError::new(ParseErrorKind::BadNumber(String::from("x")), "x").at_line(3).at_column(0),
+        ]));
+    }
+}
+
+/// Parse every integer in the line, with its sign, ignoring whatever is between them. Note that a
+/// range like `5-10` reads as `5` and `-10`.
+///
+pub fn signed_ints<T>(line: &str) -> Result<Vec<T>, ParseError>
+    where T: FromStr, T::Err: IntoParseError {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"[-+]?\d+").unwrap();
+    }
+    RE.find_iter(line)
+        .map(|m| T::from_str(m.as_str()).map_err(|e| e.into_parse_error(m.as_str(), line, m.start())))
+        .collect()
+}
+
+#[cfg(test)]
+mod signed_ints_tests {
+    use super::*;
+
+    #[test]
+    fn every_integer_in_the_line() {
+        assert_eq!(signed_ints::<i64>("#1 @ 1,3: 4x4"), Ok(vec![1, 1, 3, 4, 4]));
+        assert_eq!(signed_ints::<i64>("pos=<-12,+3,0>, r=40"), Ok(vec![-12, 3, 0, 40]));
+        assert_eq!(signed_ints::<i64>("no numbers"), Ok(vec![]));
+    }
+
+    #[test]
+    fn number_out_of_range() {
+        assert_eq!(signed_ints::<u8>("x=1, y=-1"),
+                   Err(ParseError::new(ParseErrorKind::BadNumber(String::from("-1")), "x=1, y=-1").at_column(7)));
+    }
+}
+
+/// Split an input into its blocks of lines separated by blank lines, each trimmed.
+///
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+    for line in input.split('\n') {
+        match (start, line.trim().is_empty()) {
+            (None, false) => start = Some(offset),
+            (Some(s), true) => {
+                blocks.push(input[s..offset].trim());
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len() + 1;
+    }
+    if let Some(s) = start {
+        blocks.push(input[s..].trim());
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod blocks_tests {
+    use super::*;
+
+    #[test]
+    fn blocks_between_blank_lines() {
+        assert_eq!(blocks("a\nb\n\nc\n \n\n\nd\ne\n"), vec!["a\nb", "c", "d\ne"]);
+        assert_eq!(blocks("\r\na\r\n\r\nb\r\n"), vec!["a", "b"]);
+        assert_eq!(blocks("\n\n"), Vec::<&str>::new());
+    }
+}
+
+/// The named capture groups of a line matched by a regex, to be parsed into the fields of a struct.
+///
+pub struct Fields<'t> {
+    captures: Captures<'t>,
+    line: &'t str,
+    expected: &'static str,
+}
+
+/// The format of a line: a regex to match it, and a DFA to find where a line that does not match
+/// goes wrong. Both are built once, so keep it in a `lazy_static`.
+///
+pub struct LinePattern {
+    regex: Regex,
+    prefix: Option<DenseDFA<Vec<usize>, usize>>,
+}
+
+impl LinePattern {
+    pub fn new(pattern: &str) -> Result<LinePattern, regex::Error> {
+        Ok(LinePattern { regex: Regex::new(pattern)?, prefix: prefix_dfa(pattern) })
+    }
+}
+
+/// Match `line` against `pattern`, or fail with a `MalformedLine` error, described by `expected`,
+/// at the point where the line stops matching.
+///
+pub fn fields<'t>(pattern: &LinePattern, line: &'t str, expected: &'static str) -> Result<Fields<'t>, ParseError> {
+    match pattern.regex.captures(line) {
+        Some(captures) => Ok(Fields { captures, line, expected }),
+        None => Err(ParseError::new(ParseErrorKind::MalformedLine(expected), line)
+            .at_column(pattern.prefix.as_ref().map_or(0, |dfa| prefix_length(dfa, line)))),
+    }
+}
+
+impl<'t> Fields<'t> {
+    /// The text of the named group, if it took part in the match.
+    pub fn str(&self, name: &str) -> Option<&'t str> {
+        self.captures.name(name).map(|m| m.as_str())
+    }
+
+    /// Parse the named group as a `T`.
+    pub fn get<T>(&self, name: &str) -> Result<T, ParseError>
+        where T: FromStr, T::Err: IntoParseError {
+        self.get_with(name, T::from_str)
+    }
+
+    /// Parse the named group with `parse`.
+    pub fn get_with<T, E, F>(&self, name: &str, parse: F) -> Result<T, ParseError>
+        where E: IntoParseError, F: Fn(&str) -> Result<T, E> {
+        self.get_opt_with(name, parse)?.ok_or_else(|| {
+            ParseError::new(ParseErrorKind::MalformedLine(self.expected), self.line).at_column(0)
+        })
+    }
+
+    /// Parse the named group as a `T`, if it took part in the match.
+    pub fn get_opt<T>(&self, name: &str) -> Result<Option<T>, ParseError>
+        where T: FromStr, T::Err: IntoParseError {
+        self.get_opt_with(name, T::from_str)
+    }
+
+    fn get_opt_with<T, E, F>(&self, name: &str, parse: F) -> Result<Option<T>, ParseError>
+        where E: IntoParseError, F: Fn(&str) -> Result<T, E> {
+        match self.captures.name(name) {
+            Some(m) => parse(m.as_str()).map(Some).map_err(|e| e.into_parse_error(m.as_str(), self.line, m.start())),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod fields_tests {
+    use super::*;
+
+    lazy_static! {
+        static ref RE: LinePattern = LinePattern::new(r"^#(?P<id>\d+) @ (?P<left>\d+),(?P<top>\d+)(?: tag=(?P<tag>\w+))?$").unwrap();
+    }
+
+    #[test]
+    fn named_groups_into_values() {
+        let fields = fields(&RE, "#7 @ 1,3", "a claim").unwrap();
+        assert_eq!(fields.get::<String>("id"), Ok(String::from("7")));
+        assert_eq!(fields.get::<u32>("left"), Ok(1));
+        assert_eq!(fields.get_opt::<String>("tag"), Ok(None));
+        assert_eq!(fields.str("top"), Some("3"));
+    }
+
+    #[test]
+    fn bad_value_is_reported_at_its_group() {
+        let fields = fields(&RE, "#7 @ 1,300 tag=x", "a claim").unwrap();
+        assert_eq!(fields.get::<u8>("top"),
+                   Err(ParseError::new(ParseErrorKind::BadNumber(String::from("300")), "#7 @ 1,300 tag=x").at_column(7)));
+    }
+
+    #[test]
+    fn unmatched_line_is_reported_where_it_stops_matching() {
+        assert_eq!(fields(&RE, "#7 @ x", "a claim").err(),
+                   Some(ParseError::new(ParseErrorKind::MalformedLine("a claim"), "#7 @ x").at_column(5)));
+    }
+}
+
+/// Define a struct that is parsed from a line by a regex, with a `FromStr` implementation that
+/// reads each field from the named group of the same name. Fields are converted with their own
+/// `FromStr`, or with the function given after `=>`, and every failure is a `ParseError`.
+///
+/// ```
+/// # #[macro_use] extern crate advent2018;
+/// # extern crate chrono;
+/// # use chrono::NaiveDateTime;
+/// regex_record! {
+///     #[derive(Debug)]
+///     struct Claim {
+///         id: u32,
+///         at: NaiveDateTime => |ts| NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M"),
+///     }
+///     from r"^#(?P<id>\d+) at (?P<at>.+)$" expecting "a claim like '#1 at 1518-11-01 00:00'"
+/// }
+/// # fn main() {
+/// let claim: Claim = "#1 at 1518-11-01 00:00".parse().unwrap();
+/// assert_eq!(claim.id, 1);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! regex_record {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident : $field_type:ty $(=> $with:expr)?),* $(,)?
+        }
+        from $pattern:literal expecting $expected:literal
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: $field_type,)*
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::parse::ParseError;
+
+            fn from_str(line: &str) -> Result<$name, $crate::parse::ParseError> {
+                $crate::lazy_static::lazy_static! {
+                    static ref RE: $crate::parse::LinePattern = $crate::parse::LinePattern::new($pattern).unwrap();
+                }
+                let fields = $crate::parse::fields(&RE, line, $expected)?;
+                Ok($name {
+                    $($field: $crate::regex_record!(@field fields, $field $(, $with)?)?,)*
+                })
+            }
+        }
+    };
+    (@field $fields:ident, $field:ident) => {
+        $fields.get(stringify!($field))
+    };
+    (@field $fields:ident, $field:ident, $with:expr) => {
+        $fields.get_with(stringify!($field), $with)
+    };
+}
+
+#[cfg(test)]
+mod regex_record_tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    regex_record! {
+        #[derive(PartialEq, Debug)]
+        struct Point {
+            name: String,
+            x: i32,
+            y: i32,
+        }
+        from r"^(?P<name>\w+)=<(?P<x>-?\d+),(?P<y>-?\d+)>$" expecting "a point like 'a=<1,-2>'"
+    }
+
+    regex_record! {
+        #[derive(PartialEq, Debug)]
+        struct Stamped {
+            at: NaiveDateTime => |ts| NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M"),
+        }
+        from r"^\[(?P<at>[^\]]+)\]" expecting "a timestamp"
+    }
+
+    #[test]
+    fn fields_from_named_groups() {
+        assert_eq!("a=<1,-2>".parse(), Ok(Point { name: String::from("a"), x: 1, y: -2 }));
+    }
+
+    #[test]
+    fn errors_are_parse_errors() {
+        assert_eq!("a=<1,99999999999>".parse::<Point>(),
+                   Err(ParseError::new(ParseErrorKind::BadNumber(String::from("99999999999")), "a=<1,99999999999>").at_column(5)));
+        assert_eq!("a=<1;2>".parse::<Point>(),
+                   Err(ParseError::new(ParseErrorKind::MalformedLine("a point like 'a=<1,-2>'"), "a=<1;2>").at_column(4)));
+    }
+
+    #[test]
+    fn fields_converted_with_a_function() {
+        assert_eq!("[1518-11-01 00:05]".parse::<Stamped>(), Ok(Stamped { at: NaiveDate::from_ymd(1518, 11, 1).and_hms(0, 5, 0) }));
+        assert_eq!("[1518-11-31 00:00]".parse::<Stamped>().err(),
+                   Some(ParseError::new(ParseErrorKind::BadTimestamp(String::from("1518-11-31 00:00")), "[1518-11-31 00:00]").at_column(1)));
+    }
+
+    #[test]
+    fn records_parse_as_lines() {
+        assert_eq!(lines::<Point>("a=<1,2>\nb=<3,4>").map(|points| points.len()), Ok(2));
+    }
+}
+
+/// The byte offset at which `text` can no longer match `pattern` from its start, or the length of
+/// `text` if it is a prefix of some match (i.e. the line ends too soon).
+///
+pub fn mismatch_column(pattern: &str, text: &str) -> usize {
+    prefix_dfa(pattern).map_or(0, |dfa| prefix_length(&dfa, text))
+}
+
+/// A DFA matching from the start of the text that `pattern` would, or `None` if the pattern is
+/// beyond what a DFA can be built for.
+fn prefix_dfa(pattern: &str) -> Option<DenseDFA<Vec<usize>, usize>> {
+    let pattern = pattern.trim_start_matches('^').trim_end_matches('$');
+    dense::Builder::new().anchored(true).build(pattern).ok()
+}
+
+/// How much of `text` the DFA gets through before it can no longer match.
+fn prefix_length(dfa: &DenseDFA<Vec<usize>, usize>, text: &str) -> usize {
+    let mut state = dfa.start_state();
+    for (i, b) in text.bytes().enumerate() {
+        state = dfa.next_state(state, b);
+        if dfa.is_dead_state(state) {
+            return i;
+        }
+    }
+    text.len()
+}
+
+/// What was wrong with a line of input.
+///
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParseErrorKind {
+    /// The line is not in the expected format, described here.
+    MalformedLine(&'static str),
+    /// A number in the line could not be parsed.
+    BadNumber(String),
+    /// A timestamp in the line could not be parsed.
+    BadTimestamp(String),
+    /// The line is well formed, but cannot come where it does.
+    UnexpectedEventOrder(String),
+    /// A character that cannot appear in the line, and a description of what can.
+    UnexpectedCharacter(char, &'static str),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::MalformedLine(expected) => write!(f, "expected {}", expected),
+            ParseErrorKind::BadNumber(number) => write!(f, "bad number '{}'", number),
+            ParseErrorKind::BadTimestamp(ts) => write!(f, "bad timestamp '{}'", ts),
+            ParseErrorKind::UnexpectedEventOrder(reason) => write!(f, "unexpected event order, {}", reason),
+            ParseErrorKind::UnexpectedCharacter(c, expected) => write!(f, "unexpected {:?}, expected {}", c, expected),
+        }
+    }
+}
+
+/// A line of input that could not be parsed.
+///
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// The line number in the input, once known.
+    pub line: Option<usize>,
+    /// The byte offset into the line where parsing failed, if known.
+    pub column: Option<usize>,
+    /// The offending line.
+    pub text: String,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, text: &str) -> ParseError {
+        ParseError { kind, line: None, column: None, text: text.to_string() }
+    }
+
+    pub fn at_column(self, column: usize) -> ParseError {
+        ParseError { column: Some(column), ..self }
+    }
+
+    pub fn at_line(self, line: usize) -> ParseError {
+        ParseError { line: Some(line), ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "Could not parse line {} '{}': {}", line, self.text, self.kind),
+            None => write!(f, "Could not parse '{}': {}", self.text, self.kind),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// An error from parsing a value in a line, which can become a `ParseError` for the line.
+///
+pub trait IntoParseError {
+    /// The error for failing to parse `value`, found at byte offset `column` of `line`.
+    fn into_parse_error(self, value: &str, line: &str, column: usize) -> ParseError;
+}
+
+impl IntoParseError for ParseIntError {
+    fn into_parse_error(self, value: &str, line: &str, column: usize) -> ParseError {
+        ParseError::new(ParseErrorKind::BadNumber(value.to_string()), line).at_column(column)
+    }
+}
+
+impl IntoParseError for ParseFloatError {
+    fn into_parse_error(self, value: &str, line: &str, column: usize) -> ParseError {
+        ParseError::new(ParseErrorKind::BadNumber(value.to_string()), line).at_column(column)
+    }
+}
+
+impl IntoParseError for chrono::ParseError {
+    fn into_parse_error(self, value: &str, line: &str, column: usize) -> ParseError {
+        ParseError::new(ParseErrorKind::BadTimestamp(value.to_string()), line).at_column(column)
+    }
+}
+
+impl IntoParseError for Infallible {
+    fn into_parse_error(self, _: &str, _: &str, _: usize) -> ParseError {
+        match self {}
+    }
+}
+
+/// A value that is itself parsed from part of a line has its column moved along to where that
+/// part is.
+impl IntoParseError for ParseError {
+    fn into_parse_error(self, _: &str, line: &str, column: usize) -> ParseError {
+        ParseError {
+            column: Some(self.column.unwrap_or(0) + column),
+            text: line.to_string(),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod numbered_lines_tests {
+    use super::*;
+
+    #[test]
+    fn blank_lines_are_skipped_but_counted() {
+        assert_eq!(numbered_lines("+1\n\n -2 \n+3\n"), vec![(1, "+1"), (3, "-2"), (4, "+3")]);
+    }
+}
+
+#[cfg(test)]
+mod parse_lines_tests {
+    use super::*;
+
+    fn to_i(line: &str) -> Result<i32, ParseError> {
+        line.parse().map_err(|_| ParseError::new(ParseErrorKind::BadNumber(line.to_string()), line))
+    }
+
+    #[test]
+    fn all_lines_parse() {
+        assert_eq!(parse_lines(&[(1, "+1"), (3, "-2")], to_i), Ok(vec![1, -2]));
+    }
+
+    #[test]
+    fn every_bad_line_is_reported() {
+        assert_eq!(parse_lines(&[(1, "x"), (2, "+1"), (3, "y")], to_i), Err(vec![
+            ParseError::new(ParseErrorKind::BadNumber(String::from("x")), "x").at_line(1),
+            ParseError::new(ParseErrorKind::BadNumber(String::from("y")), "y").at_line(3),
+        ]));
+    }
+}
+
+#[cfg(test)]
+mod mismatch_column_tests {
+    use super::*;
+
+    #[test]
+    fn column_of_first_unexpected_character() {
+        assert_eq!(mismatch_column(r"^#(\d+) @ (\d+),(\d+): (\d+)x(\d+)", "#12 @ 3,x: 4x4"), 8);
+        assert_eq!(mismatch_column(r"^#(\d+) @ (\d+),(\d+): (\d+)x(\d+)", "rubbish"), 0);
+    }
+
+    #[test]
+    fn line_ending_too_soon_points_past_its_end() {
+        assert_eq!(mismatch_column(r"^\[([\d\-: ]+)\] (.+)$", "[1518-11-01 00:00]"), 18);
+    }
+}
+
+#[cfg(test)]
+mod parse_error_tests {
+    use super::*;
+
+    #[test]
+    fn display_with_and_without_line() {
+        let err = ParseError::new(ParseErrorKind::BadNumber(String::from("x")), "#12 @ 3,x: 4x4");
+        assert_eq!(err.to_string(), "Could not parse '#12 @ 3,x: 4x4': bad number 'x'");
+        assert_eq!(err.at_line(7).to_string(), "Could not parse line 7 '#12 @ 3,x: 4x4': bad number 'x'");
+    }
+}
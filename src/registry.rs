@@ -0,0 +1,67 @@
+use solver::Solver;
+use day01;
+use day02;
+use day03;
+use day04;
+use day05;
+
+/// Every solver registered for the year, in calendar order.
+///
+pub fn all(year: u16) -> Vec<Box<dyn Solver>> {
+    match year {
+        2018 => vec![
+            Box::new(day01::Day01),
+            Box::new(day02::Day02),
+            Box::new(day03::Day03),
+            Box::new(day04::Day04),
+            Box::new(day05::Day05),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+/// Find the solver registered for the given day number of the year.
+///
+pub fn find(year: u16, day: u8) -> Option<Box<dyn Solver>> {
+    all(year).into_iter().find(|s| s.day() == day)
+}
+
+/// A comma separated list of the day numbers registered for the year, for error messages.
+///
+pub fn available(year: u16) -> String {
+    let days = all(year).iter().map(|s| s.day().to_string()).collect::<Vec<String>>();
+    if days.is_empty() { String::from("none") } else { days.join(", ") }
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+
+    #[test]
+    fn find_registered_day() {
+        assert_eq!(find(2018, 3).map(|s| s.day()), Some(3));
+    }
+
+    #[test]
+    fn find_unregistered_day() {
+        assert!(find(2018, 25).is_none());
+        assert!(find(2019, 3).is_none());
+    }
+
+    #[test]
+    fn available_lists_all_days() {
+        let days: Vec<String> = all(2018).iter().map(|s| s.day().to_string()).collect();
+        assert_eq!(available(2018), days.join(", "));
+        assert!(available(2018).starts_with("1, 2, 3, 4, 5"));
+        assert_eq!(available(2019), "none");
+    }
+
+    #[test]
+    fn registered_days_are_unique_and_in_order() {
+        let days: Vec<u8> = all(2018).iter().map(|s| s.day()).collect();
+        let mut sorted = days.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(days, sorted);
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use run::{self, RunResult};
+use solver::Answer;
+
+/// How run results are written to stdout.
+///
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Format {
+    /// For people: the bare answer, or a table when running every day.
+    Text,
+    /// A JSON array with an object per result.
+    Json,
+    /// A header row, then a row per result.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format '{}', expected text, json or csv", s)),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "year,day,part,answer,error,input_hash,elapsed_ns";
+
+/// The results in the given format. Each has its year, day, part, answer or error, input hash and
+/// time taken in nanoseconds, with those that do not apply left null or empty.
+///
+pub fn render(format: Format, year: u16, results: &[RunResult]) -> String {
+    match format {
+        Format::Text => run::render_table(results),
+        Format::Json => {
+            let records = results.iter().map(|r| format!(
+                "  {{\"year\": {}, \"day\": {}, \"part\": {}, \"answer\": {}, \"error\": {}, \"input_hash\": {}, \"elapsed_ns\": {}}}",
+                year, r.day, r.part,
+                r.answer.as_ref().ok().map_or_else(|| String::from("null"), json_answer),
+                json_or_null(r.answer.as_ref().err()),
+                json_or_null(r.input_hash.as_ref()),
+                r.elapsed.as_nanos(),
+            )).collect::<Vec<String>>();
+            if records.is_empty() { String::from("[]\n") } else { format!("[\n{}\n]\n", records.join(",\n")) }
+        }
+        Format::Csv => {
+            let mut csv = format!("{}\n", CSV_HEADER);
+            for r in results {
+                let _ = writeln!(csv, "{},{},{},{},{},{},{}", year, r.day, r.part,
+                                 csv_field(&r.answer.as_ref().map(Answer::to_string).unwrap_or_default()),
+                                 csv_field(r.answer.as_ref().err().map_or("", String::as_str)),
+                                 r.input_hash.as_ref().map_or("", String::as_str),
+                                 r.elapsed.as_nanos());
+            }
+            csv
+        }
+    }
+}
+
+/// Integers as JSON numbers, so they can be compared without parsing, and anything else as a string.
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Integer(n) => n.to_string(),
+        Answer::Text(_) | Answer::Grid(_) => json_string(&answer.to_string()),
+    }
+}
+
+fn json_or_null(value: Option<&String>) -> String {
+    value.map_or_else(|| String::from("null"), |v| json_string(v))
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => { let _ = write!(json, "\\u{:04x}", c as u32); }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Quote a field if it holds anything that would break the row apart.
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod render_tests {
+    use super::*;
+    use solver::Part;
+    use std::time::Duration;
+
+    fn results() -> Vec<RunResult> {
+        vec![
+            RunResult { day: 5, part: Part::One, answer: Ok(Answer::from(10)), input_hash: Some(String::from("af63dc4c8601ec8c")), elapsed: Duration::from_micros(1500) },
+            RunResult { day: 5, part: Part::Two, answer: Err(String::from("Bad input: unexpected '\"', expected a letter")), input_hash: None, elapsed: Duration::from_nanos(0) },
+        ]
+    }
+
+    #[test]
+    fn formats_by_name() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("xml".parse::<Format>(), Err(String::from("Unknown format 'xml', expected text, json or csv")));
+    }
+
+    #[test]
+    fn json_array_of_results() {
+        assert_eq!(render(Format::Json, 2018, &results()), "[
+  {\"year\": 2018, \"day\": 5, \"part\": 1, \"answer\": 10, \"error\": null, \"input_hash\": \"af63dc4c8601ec8c\", \"elapsed_ns\": 1500000},
+  {\"year\": 2018, \"day\": 5, \"part\": 2, \"answer\": null, \"error\": \"Bad input: unexpected '\\\"', expected a letter\", \"input_hash\": null, \"elapsed_ns\": 0}
+]
+");
+        assert_eq!(render(Format::Json, 2018, &[]), "[]\n");
+    }
+
+    #[test]
+    fn csv_with_header_and_quoted_fields() {
+        assert_eq!(render(Format::Csv, 2018, &results()), "\
+year,day,part,answer,error,input_hash,elapsed_ns
+2018,5,1,10,,af63dc4c8601ec8c,1500000
+2018,5,2,,\"Bad input: unexpected '\"\"', expected a letter\",,0
+");
+    }
+
+    #[test]
+    fn control_characters_are_escaped() {
+        assert_eq!(json_string("a\tb\u{1}"), "\"a\\tb\\u0001\"");
+    }
+}
@@ -0,0 +1,176 @@
+use std::any::Any;
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+use solver::{Answer, Part, Solver};
+use timing::{self, format_duration};
+
+/// The outcome of running one part of one day.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub struct RunResult {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Result<Answer, String>,
+    /// Identifies the input the part was run against, if it could be read.
+    pub input_hash: Option<String>,
+    pub elapsed: Duration,
+}
+
+/// Solve a part, turning a panicking solver into an error rather than taking the process down.
+///
+pub fn run_part(solver: &dyn Solver, part: Part, input: &str) -> RunResult {
+    let (outcome, elapsed) = timing::time(|| panic::catch_unwind(AssertUnwindSafe(|| solver.solve(part, input))));
+    let answer = match outcome {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(cause) => Err(format!("Solver panicked: {}", panic_message(&cause))),
+    };
+    RunResult { day: solver.day(), part, answer, input_hash: Some(input_hash(input)), elapsed }
+}
+
+/// A 64-bit FNV-1a hash of the input as hex, so runs against the same input can be matched up
+/// without the input itself, which the site asks not to be shared.
+///
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3));
+    format!("{:016x}", hash)
+}
+
+fn panic_message(cause: &Box<dyn Any + Send>) -> String {
+    if let Some(msg) = cause.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = cause.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        String::from("unknown cause")
+    }
+}
+
+/// Run both parts of every solver, reading each day's input with `read_input`.
+///
+/// A day whose input cannot be read, or whose solver fails, is recorded as an error and the rest
+/// still run.
+///
+pub fn run_all<F>(solvers: &[Box<dyn Solver>], read_input: F) -> Vec<RunResult>
+    where F: Fn(u8) -> Result<String, Box<dyn Error>> {
+    let mut results = Vec::new();
+    for solver in solvers {
+        match read_input(solver.day()) {
+            Ok(input) => {
+                results.push(run_part(solver.as_ref(), Part::One, &input));
+                results.push(run_part(solver.as_ref(), Part::Two, &input));
+            }
+            Err(e) => for part in &[Part::One, Part::Two] {
+                results.push(RunResult {
+                    day: solver.day(),
+                    part: *part,
+                    answer: Err(format!("Could not read input: {}", e)),
+                    input_hash: None,
+                    elapsed: Duration::from_secs(0),
+                });
+            },
+        }
+    }
+    results
+}
+
+/// A table of the results, one row per day and part, with the total time taken.
+///
+pub fn render_table(results: &[RunResult]) -> String {
+    let mut table = format!("{:>3}  {:>4}  {:>10}  {}\n", "Day", "Part", "Time", "Answer");
+    for result in results {
+        let answer = match result.answer {
+            // a grid's rows line up under its first
+            Ok(ref answer) => answer.to_string().replace('\n', &format!("\n{:23}", "")),
+            Err(ref e) => format!("ERROR: {}", e),
+        };
+        table.push_str(&format!("{:>3}  {:>4}  {:>10}  {}\n", result.day, result.part, format_duration(result.elapsed), answer));
+    }
+    let total = results.iter().map(|r| r.elapsed).sum();
+    let failed = results.iter().filter(|r| r.answer.is_err()).count();
+    table.push_str(&format!("Total     {:>10}  {} solved, {} failed\n", format_duration(total), results.len() - failed, failed));
+    table
+}
+
+#[cfg(test)]
+mod run_tests {
+    use super::*;
+    use solver::SolveError;
+    use day05::Day05;
+
+    struct Panicking;
+
+    impl Solver for Panicking {
+        fn day(&self) -> u8 {
+            99
+        }
+
+        fn title(&self) -> &'static str {
+            "Panicking"
+        }
+
+        fn parse(&self, _input: &str) -> Result<usize, SolveError> {
+            Ok(0)
+        }
+
+        fn part_one(&self, _input: &str) -> Result<Answer, SolveError> {
+            panic!("Could not parse new event")
+        }
+
+        fn part_two(&self, _input: &str) -> Result<Answer, SolveError> {
+            Err(SolveError::NoSolution(String::from("No guard found!")))
+        }
+    }
+
+    #[test]
+    fn failures_are_captured_and_later_days_still_run() {
+        let solvers: Vec<Box<dyn Solver>> = vec![Box::new(Panicking), Box::new(Day05)];
+        let results = run_all(&solvers, |_| Ok(String::from("dabAcCaCBAcCcaDA")));
+        let answers: Vec<(u8, Part, Result<Answer, String>)> = results.into_iter().map(|r| (r.day, r.part, r.answer)).collect();
+        assert_eq!(answers, vec![
+            (99, Part::One, Err(String::from("Solver panicked: Could not parse new event"))),
+            (99, Part::Two, Err(String::from("No solution: No guard found!"))),
+            (5, Part::One, Ok(Answer::from(10))),
+            (5, Part::Two, Ok(Answer::from(4))),
+        ]);
+    }
+
+    #[test]
+    fn unreadable_input_fails_both_parts() {
+        let solvers: Vec<Box<dyn Solver>> = vec![Box::new(Day05)];
+        let results = run_all(&solvers, |day| Err(From::from(format!("no input for day {}", day))));
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.answer == Err(String::from("Could not read input: no input for day 5"))));
+        assert!(results.iter().all(|r| r.input_hash.is_none()));
+    }
+
+    #[test]
+    fn input_hash_is_fnv_1a() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+        assert_eq!(run_part(&Day05, Part::One, "dabAcCaCBAcCcaDA").input_hash, Some(input_hash("dabAcCaCBAcCcaDA")));
+    }
+
+    #[test]
+    fn table_has_a_row_per_result_and_a_total() {
+        let results = vec![
+            RunResult { day: 5, part: Part::One, answer: Ok(Answer::from(10)), input_hash: None, elapsed: Duration::from_millis(2) },
+            RunResult { day: 5, part: Part::Two, answer: Err(String::from("boom")), input_hash: None, elapsed: Duration::from_millis(3) },
+        ];
+        assert_eq!(render_table(&results), "\
+Day  Part        Time  Answer
+  5     1     2.000ms  10
+  5     2     3.000ms  ERROR: boom
+Total        5.000ms  1 solved, 1 failed
+");
+    }
+
+    #[test]
+    fn grid_rows_line_up_in_the_table() {
+        let grid = Answer::Grid(vec![String::from("#..#"), String::from("####")]);
+        let results = vec![RunResult { day: 10, part: Part::One, answer: Ok(grid), input_hash: None, elapsed: Duration::from_millis(1) }];
+        assert!(render_table(&results).contains(" 10     1     1.000ms  #..#\n                       ####\n"));
+    }
+}
@@ -0,0 +1,286 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use markdown;
+
+/// The title given to a new day when its puzzle page has not been downloaded.
+///
+pub const PLACEHOLDER_TITLE: &str = "Untitled";
+
+/// The test running the checked-in fixtures, relative to the root of the checkout.
+///
+pub const EXAMPLES_TEST: &str = "tests/examples.rs";
+
+const AWAITING_FIXTURES: &str = "const AWAITING_FIXTURES: &[(u16, u8)] = &[";
+
+/// The source of a new day's module: a solver with both parts unsolved, and an ignored test for
+/// each part ready for its worked example.
+///
+pub fn day_module(year: u16, day: u8, title: &str) -> String {
+    format!(r#"use parse::{{self, ParseError}};
+use solver::{{Answer, SolveError, Solver}};
+
+fn parse_input(input: &str) -> Result<Vec<String>, Vec<ParseError>> {{
+    parse::lines(input)
+}}
+
+fn solve_part_one(input: &str) -> Result<Answer, SolveError> {{
+    let _records = parse_input(input)?;
+    Err(SolveError::NoSolution(String::from("Part one is not solved yet")))
+}}
+
+#[cfg(test)]
+mod part_one_tests {{
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn part_one_acceptance_test() {{
+        assert_eq!(solve_part_one("").unwrap(), "");
+    }}
+}}
+
+fn solve_part_two(input: &str) -> Result<Answer, SolveError> {{
+    let _records = parse_input(input)?;
+    Err(SolveError::NoSolution(String::from("Part two is not solved yet")))
+}}
+
+#[cfg(test)]
+mod part_two_tests {{
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn part_two_acceptance_test() {{
+        assert_eq!(solve_part_two("").unwrap(), "");
+    }}
+}}
+
+pub struct {name};
+
+impl Solver for {name} {{
+    fn day(&self) -> u8 {{
+        {day}
+    }}
+
+    fn title(&self) -> &'static str {{
+        {title:?}
+    }}
+
+    fn parse(&self, input: &str) -> Result<usize, SolveError> {{
+        Ok(parse_input(input)?.len())
+    }}
+
+    fn part_one(&self, input: &str) -> Result<Answer, SolveError> {{
+        solve_part_one(input)
+    }}
+
+    fn part_two(&self, input: &str) -> Result<Answer, SolveError> {{
+        solve_part_two(input)
+    }}
+}}
+"#, name = struct_name(year, day), day = day, title = title)
+}
+
+/// Generated days are named for their year too, e.g. `day2019_06`, so each year can have a day 6.
+fn module_name(year: u16, day: u8) -> String {
+    format!("day{}_{:02}", year, day)
+}
+
+fn struct_name(year: u16, day: u8) -> String {
+    format!("Day{}_{:02}", year, day)
+}
+
+/// The puzzle's title from its page, e.g. `Chronal Coordinates` from
+/// `--- Day 6: Chronal Coordinates ---`.
+///
+pub fn title(page: &str) -> Option<String> {
+    let start = page.find("<h2>")? + "<h2>".len();
+    let end = page[start..].find("</h2>")? + start;
+    let heading = markdown::text_of(&page[start..end]);
+    let title = heading.trim_matches(|c: char| c == '-' || c.is_whitespace());
+    title.find(": ").map(|i| title[i + 2..].to_string())
+}
+
+/// Add `pub mod dayYYYY_NN;` to the library's module list, keeping the days in order.
+///
+pub fn register_module(lib_src: &str, year: u16, day: u8) -> Result<String, String> {
+    let line = format!("pub mod {};", module_name(year, day));
+    insert_in_order(lib_src, |l| l.starts_with("pub mod day") && l.ends_with(';'), &line)
+        .ok_or_else(|| String::from("No day modules found to add the new one to"))
+}
+
+/// Add the new day's solver to the year's list in the registry, adding the year if it has none.
+///
+pub fn register_solver(registry_src: &str, year: u16, day: u8) -> Result<String, String> {
+    let use_line = format!("use {};", module_name(year, day));
+    let src = insert_in_order(registry_src, |l| l.starts_with("use day"), &use_line)
+        .ok_or_else(|| String::from("No day imports found in the registry to add the new one to"))?;
+
+    let entry = format!("            Box::new({}::{}),", module_name(year, day), struct_name(year, day));
+    let arm = format!("        {} => vec![", year);
+    let mut lines: Vec<String> = src.lines().map(str::to_string).collect();
+    match lines.iter().position(|l| *l == arm) {
+        Some(start) => {
+            let end = lines[start..].iter().position(|l| l.trim() == "],")
+                .map(|i| start + i)
+                .ok_or_else(|| format!("The solver list for {} in the registry is not closed", year))?;
+            let at = (start + 1..end).find(|i| lines[*i] > entry).unwrap_or(end);
+            lines.insert(at, entry);
+        }
+        None => {
+            let fallback = lines.iter().position(|l| l.trim() == "_ => Vec::new(),")
+                .ok_or_else(|| String::from("No fallback arm found in the registry to add the year before"))?;
+            lines.splice(fallback..fallback, vec![arm, entry, String::from("        ],")]);
+        }
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add the day to the examples test's list of days that have no fixtures yet, so that the test
+/// passes until they are saved.
+///
+pub fn register_awaiting_fixtures(test_src: &str, year: u16, day: u8) -> Result<String, String> {
+    let entry = format!("    ({}, {}),", year, day);
+    let mut lines: Vec<&str> = test_src.lines().collect();
+    let start = lines.iter().position(|l| *l == AWAITING_FIXTURES)
+        .ok_or_else(|| String::from("No AWAITING_FIXTURES list found to add the new day to"))?;
+    let end = lines[start..].iter().position(|l| *l == "];")
+        .map(|i| start + i)
+        .ok_or_else(|| String::from("The AWAITING_FIXTURES list is not closed"))?;
+    if !lines[start + 1..end].contains(&entry.as_str()) {
+        lines.insert(end, &entry);
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Insert `new_line` among the consecutive lines matching `is_peer`, before the first one that
+/// sorts after it.
+fn insert_in_order<F: Fn(&str) -> bool>(src: &str, is_peer: F, new_line: &str) -> Option<String> {
+    let mut lines: Vec<&str> = src.lines().collect();
+    let first = lines.iter().position(|l| is_peer(l))?;
+    let end = lines[first..].iter().position(|l| !is_peer(l)).map_or(lines.len(), |i| first + i);
+    let at = (first..end).find(|i| lines[*i] > new_line).unwrap_or(end);
+    lines.insert(at, new_line);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Write the new day's module under `src_dir` and register it with the library and the registry,
+/// giving the path of the module. An existing module is never replaced.
+///
+pub fn create_day(src_dir: &Path, year: u16, day: u8, title: &str) -> Result<PathBuf, String> {
+    let module_path = src_dir.join(format!("{}.rs", module_name(year, day)));
+    let lib_path = src_dir.join("lib.rs");
+    let registry_path = src_dir.join("registry.rs");
+    if !lib_path.exists() || !registry_path.exists() {
+        return Err(format!("No lib.rs and registry.rs in {}, run new from the root of the checkout", src_dir.display()));
+    }
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+
+    let read = |path: &Path| fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e));
+    let lib_src = register_module(&read(&lib_path)?, year, day)?;
+    let registry_src = register_solver(&read(&registry_path)?, year, day)?;
+
+    let write = |path: &Path, contents: &str| fs::write(path, contents).map_err(|e| format!("{}: {}", path.display(), e));
+    write(&module_path, &day_module(year, day, title))?;
+    write(&lib_path, &lib_src)?;
+    write(&registry_path, &registry_src)?;
+    Ok(module_path)
+}
+
+/// List the day as awaiting fixtures in the examples test at `test_path`.
+///
+pub fn await_fixtures(test_path: &Path, year: u16, day: u8) -> Result<(), String> {
+    let test_src = fs::read_to_string(test_path).map_err(|e| format!("{}: {}", test_path.display(), e))?;
+    let test_src = register_awaiting_fixtures(&test_src, year, day)?;
+    fs::write(test_path, test_src).map_err(|e| format!("{}: {}", test_path.display(), e))
+}
+
+#[cfg(test)]
+mod scaffold_tests {
+    use super::*;
+    use temp_dir::TempDir;
+
+    const LIB: &str = "#[macro_use]\npub mod parse;\npub mod verify;\npub mod day01;\npub mod day05;\n\n#[cfg(test)]\nmod mock_http;\n";
+
+    const REGISTRY: &str = "\
+use solver::Solver;
+use day01;
+use day05;
+
+pub fn all(year: u16) -> Vec<Box<dyn Solver>> {
+    match year {
+        2018 => vec![
+            Box::new(day01::Day01),
+            Box::new(day05::Day05),
+        ],
+        _ => Vec::new(),
+    }
+}
+";
+
+    #[test]
+    fn module_is_added_in_order() {
+        let lib = register_module(LIB, 2018, 12).unwrap();
+        assert_eq!(lib, "#[macro_use]\npub mod parse;\npub mod verify;\npub mod day01;\npub mod day05;\npub mod day2018_12;\n\n#[cfg(test)]\nmod mock_http;\n");
+        assert!(register_module(&lib, 2018, 6).unwrap().contains("pub mod day05;\npub mod day2018_06;\npub mod day2018_12;\n\n"));
+        assert_eq!(register_module("pub mod parse;\n", 2018, 3), Err(String::from("No day modules found to add the new one to")));
+    }
+
+    #[test]
+    fn solver_is_added_to_the_year_in_order() {
+        let registry = register_solver(REGISTRY, 2018, 12).unwrap();
+        let registry = register_solver(&registry, 2018, 6).unwrap();
+        assert!(registry.contains("use day05;\nuse day2018_06;\nuse day2018_12;\n"));
+        assert!(registry.contains("            Box::new(day05::Day05),\n            Box::new(day2018_06::Day2018_06),\n            Box::new(day2018_12::Day2018_12),\n        ],\n"));
+    }
+
+    #[test]
+    fn solver_for_a_new_year_gets_its_own_list() {
+        let registry = register_solver(REGISTRY, 2019, 6).unwrap();
+        assert!(registry.contains("use day05;\nuse day2019_06;\n"));
+        assert!(registry.contains("        ],\n        2019 => vec![\n            Box::new(day2019_06::Day2019_06),\n        ],\n        _ => Vec::new(),\n"));
+    }
+
+    #[test]
+    fn day_is_added_to_those_awaiting_fixtures_once() {
+        let test_src = "const AWAITING_FIXTURES: &[(u16, u8)] = &[\n];\n\n#[test]\nfn checked_in_fixtures_pass() {}\n";
+        let test_src = register_awaiting_fixtures(test_src, 2018, 6).unwrap();
+        let test_src = register_awaiting_fixtures(&test_src, 2019, 6).unwrap();
+        assert_eq!(register_awaiting_fixtures(&test_src, 2018, 6).unwrap(), test_src);
+        assert!(test_src.starts_with("const AWAITING_FIXTURES: &[(u16, u8)] = &[\n    (2018, 6),\n    (2019, 6),\n];\n"));
+        assert_eq!(register_awaiting_fixtures("fn main() {}\n", 2018, 6),
+                   Err(String::from("No AWAITING_FIXTURES list found to add the new day to")));
+    }
+
+    #[test]
+    fn title_from_the_page_heading() {
+        assert_eq!(title("<article class=\"day-desc\"><h2>--- Day 6: Chronal Coordinates ---</h2><p>...</p>"),
+                   Some(String::from("Chronal Coordinates")));
+        assert_eq!(title("<p>Please log in</p>"), None);
+    }
+
+    #[test]
+    fn created_day_is_registered_and_never_replaced() {
+        let dir = TempDir::new("scaffold");
+        let src_dir = dir.path();
+        fs::write(src_dir.join("lib.rs"), LIB).unwrap();
+        fs::write(src_dir.join("registry.rs"), REGISTRY).unwrap();
+
+        let module_path = src_dir.join("day2018_06.rs");
+        assert_eq!(create_day(src_dir, 2018, 6, "Chronal \"Coordinates\""), Ok(module_path.clone()));
+        let module = fs::read_to_string(&module_path).unwrap();
+        assert!(module.contains("pub struct Day2018_06;"));
+        assert!(module.contains("        6\n"));
+        assert!(module.contains("        \"Chronal \\\"Coordinates\\\"\"\n"));
+        assert!(fs::read_to_string(src_dir.join("lib.rs")).unwrap().contains("pub mod day2018_06;"));
+        assert!(fs::read_to_string(src_dir.join("registry.rs")).unwrap().contains("Box::new(day2018_06::Day2018_06),"));
+
+        assert_eq!(create_day(src_dir, 2018, 6, PLACEHOLDER_TITLE),
+                   Err(format!("{} already exists", module_path.display())));
+        assert_eq!(create_day(src_dir, 2019, 6, PLACEHOLDER_TITLE), Ok(src_dir.join("day2019_06.rs")));
+        assert!(fs::read_to_string(src_dir.join("lib.rs")).unwrap().contains("pub mod day2018_06;\npub mod day2019_06;\n"));
+    }
+}
@@ -1,5 +1,236 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use parse::ParseError;
+
+/// The answer to one part of a day's puzzle.
+///
+/// Answers of different kinds are equal when they are written the same, so an answer read back
+/// from a file matches the one a solver gave.
+///
+#[derive(Eq, Clone, Debug)]
+pub enum Answer {
+    /// A number, which can be compared with others by value.
+    Integer(i64),
+    /// A single line, like a box ID.
+    Text(String),
+    /// Rows of characters, like letters drawn by lights, to be read off by eye before submitting.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Read an answer as written by `Display`: an integer if it is one, several lines as a grid,
+    /// and text otherwise.
+    pub fn parse(s: &str) -> Answer {
+        match s.parse::<i64>() {
+            Ok(n) if n.to_string() == s => Answer::Integer(n),
+            _ if s.contains('\n') => Answer::Grid(s.lines().map(str::to_string).collect()),
+            _ => Answer::Text(s.to_string()),
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Answer::Integer(n) => Some(*n),
+            Answer::Text(_) | Answer::Grid(_) => None,
+        }
+    }
+
+    /// How two integer answers compare, which says nothing for other kinds.
+    pub fn numeric_cmp(&self, other: &Answer) -> Option<Ordering> {
+        Some(self.as_integer()?.cmp(&other.as_integer()?))
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Integer(a), Answer::Integer(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Answer {
+    fn eq(&self, other: &&'a str) -> bool {
+        *self == Answer::parse(other)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! integer_answers {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Integer(n as i64)
+                }
+            }
+        )*
+    };
+}
+
+integer_answers!(i32, i64, u32, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl<'a> From<&'a str> for Answer {
+    fn from(text: &'a str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod answer_tests {
+    use super::*;
+
+    #[test]
+    fn parse_by_kind() {
+        assert_eq!(Answer::parse("-42").as_integer(), Some(-42));
+        assert_eq!(Answer::parse("0042").as_integer(), None);
+        assert_eq!(Answer::parse("fgij"), Answer::Text(String::from("fgij")));
+        assert_eq!(Answer::parse("#.#\n.#."), Answer::Grid(vec![String::from("#.#"), String::from(".#.")]));
+    }
+
+    #[test]
+    fn equal_when_written_the_same() {
+        assert_eq!(Answer::from(240u32), Answer::Text(String::from("240")));
+        assert_eq!(Answer::from(240u32), "240");
+        assert_ne!(Answer::from(240u32), Answer::from(241));
+        assert_eq!(Answer::parse("ab\ncd").to_string(), "ab\ncd");
+    }
+
+    #[test]
+    fn integers_compare_by_value() {
+        assert_eq!(Answer::from(9).numeric_cmp(&Answer::from(10)), Some(Ordering::Less));
+        assert_eq!(Answer::from(9).numeric_cmp(&Answer::from("10")), None);
+    }
+}
+
+/// Why a solver could not produce an answer for its input.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub enum SolveError {
+    /// The puzzle input could not be parsed, for every one of these reasons.
+    Parse(Vec<ParseError>),
+    /// The input parsed, but holds no answer.
+    NoSolution(String),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Parse(errors) => match errors.len() {
+                0 => write!(f, "Bad input"),
+                1 => write!(f, "Bad input: {}", errors[0]),
+                n => write!(f, "Bad input: {} (and {} more bad lines)", errors[0], n - 1),
+            },
+            SolveError::NoSolution(msg) => write!(f, "No solution: {}", msg),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> SolveError {
+        SolveError::Parse(vec![e])
+    }
+}
+
+impl From<Vec<ParseError>> for SolveError {
+    fn from(errors: Vec<ParseError>) -> SolveError {
+        SolveError::Parse(errors)
+    }
+}
+
+/// One of the two parts of every day's puzzle.
+///
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.number(), f)
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Part, String> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Unknown part '{}', expected 1 or 2", s)),
+        }
+    }
+}
+
+/// A solver for both parts of a single day's puzzle.
+///
 pub trait Solver {
-    fn part_one(input: &String) -> String;
+    /// The day of the calendar this solver is for.
+    fn day(&self) -> u8;
+
+    /// The puzzle's title, as shown on its page.
+    fn title(&self) -> &'static str;
+
+    /// Run only the parsing stage on the input, returning how many records it holds.
+    fn parse(&self, input: &str) -> Result<usize, SolveError>;
+
+    fn part_one(&self, input: &str) -> Result<Answer, SolveError>;
+
+    fn part_two(&self, input: &str) -> Result<Answer, SolveError>;
+
+    /// Solve the given part of the puzzle.
+    fn solve(&self, part: Part, input: &str) -> Result<Answer, SolveError> {
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod part_tests {
+    use super::*;
+
+    #[test]
+    fn parse_parts() {
+        assert_eq!("1".parse::<Part>(), Ok(Part::One));
+        assert_eq!("2".parse::<Part>(), Ok(Part::Two));
+    }
 
-    fn part_two(input: &String) -> String;
+    #[test]
+    fn parse_unknown_part_should_produce_err_result() {
+        assert_eq!("3".parse::<Part>(), Err(String::from("Unknown part '3', expected 1 or 2")));
+    }
 }
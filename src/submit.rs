@@ -0,0 +1,166 @@
+use regex::Regex;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use http::Client;
+use solver::{Answer, Part};
+
+/// The site's verdict on a submitted answer.
+///
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The part has already been solved, so the answer was not checked.
+    AlreadySolved,
+    /// An answer was submitted too recently; nothing can be submitted for this long.
+    RateLimited(Duration),
+}
+
+impl Verdict {
+    /// Classify the HTML page returned from an answer submission.
+    pub fn classify(page: &str) -> Result<Verdict, String> {
+        lazy_static! {
+            static ref WAIT_RE: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        }
+
+        if page.contains("That's the right answer") {
+            Ok(Verdict::Correct)
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Ok(Verdict::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Ok(Verdict::TooLow)
+            } else {
+                Ok(Verdict::Incorrect)
+            }
+        } else if page.contains("You don't seem to be solving the right level") {
+            Ok(Verdict::AlreadySolved)
+        } else if page.contains("You gave an answer too recently") {
+            let wait = WAIT_RE.captures(page).map(|cap| {
+                let minutes = cap.get(1).map_or(0, |m| m.as_str().parse::<u64>().unwrap_or(0));
+                let seconds = cap.get(2).map_or(0, |m| m.as_str().parse::<u64>().unwrap_or(0));
+                minutes * 60 + seconds
+            }).unwrap_or(0);
+            Ok(Verdict::RateLimited(Duration::from_secs(wait)))
+        } else {
+            Err(String::from("Could not recognise the response to the submitted answer"))
+        }
+    }
+
+    /// Whether the part is solved after this verdict.
+    pub fn is_solved(&self) -> bool {
+        matches!(self, Verdict::Correct | Verdict::AlreadySolved)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::Incorrect => write!(f, "That's not the right answer."),
+            Verdict::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            Verdict::TooLow => write!(f, "That's not the right answer, it is too low."),
+            Verdict::AlreadySolved => write!(f, "This part has already been solved."),
+            Verdict::RateLimited(wait) => write!(f, "Answered too recently, wait {}s before trying again.", wait.as_secs()),
+        }
+    }
+}
+
+/// Submit an answer for one part of a day, returning the site's verdict. A grid is never sent, as
+/// the site wants the letters it shows, which only a person can read off.
+///
+pub fn submit_answer(client: &Client, year: u16, day: u8, part: Part, answer: &Answer) -> Result<Verdict, Box<dyn Error>> {
+    if let Answer::Grid(_) = answer {
+        return Err(From::from("A grid answer has to be read off and submitted by hand"));
+    }
+    let page = client.post_form(&format!("/{}/day/{}/answer", year, day),
+                                &[("level", part.to_string().as_str()), ("answer", answer.to_string().as_str())])?;
+    Ok(Verdict::classify(&page)?)
+}
+
+#[cfg(test)]
+mod classify_tests {
+    use super::*;
+
+    fn page(main: &str) -> String {
+        format!("<!DOCTYPE html>\n<html lang=\"en-us\"><body><main>\n<article><p>{}</p></article>\n</main></body></html>", main)
+    }
+
+    #[test]
+    fn correct_answer() {
+        assert_eq!(Verdict::classify(&page("That's the right answer!  You are one gold star closer to fixing the time stream.")),
+                   Ok(Verdict::Correct));
+    }
+
+    #[test]
+    fn incorrect_answers() {
+        assert_eq!(Verdict::classify(&page("That's not the right answer.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again.")),
+                   Ok(Verdict::Incorrect));
+        assert_eq!(Verdict::classify(&page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again.")),
+                   Ok(Verdict::TooHigh));
+        assert_eq!(Verdict::classify(&page("That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again.")),
+                   Ok(Verdict::TooLow));
+    }
+
+    #[test]
+    fn already_solved() {
+        assert_eq!(Verdict::classify(&page("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2018/day/5\">[Return to Day 5]</a>")),
+                   Ok(Verdict::AlreadySolved));
+    }
+
+    #[test]
+    fn rate_limited_with_wait_time() {
+        assert_eq!(Verdict::classify(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait.")),
+                   Ok(Verdict::RateLimited(Duration::from_secs(37))));
+        assert_eq!(Verdict::classify(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 2s left to wait.")),
+                   Ok(Verdict::RateLimited(Duration::from_secs(242))));
+    }
+
+    #[test]
+    fn unrecognised_page_should_produce_err_result() {
+        assert_eq!(Verdict::classify(&page("Something else entirely")),
+                   Err(String::from("Could not recognise the response to the submitted answer")));
+    }
+}
+
+#[cfg(test)]
+mod submit_answer_tests {
+    use super::*;
+    use http::DEFAULT_TIMEOUT;
+    use mock_http::MockServer;
+
+    fn client(server: &MockServer, sess: &str) -> Client {
+        Client::new(&server.base_url, Some(sess), DEFAULT_TIMEOUT).unwrap()
+    }
+
+    #[test]
+    fn posts_answer_with_session_and_classifies_response() {
+        let server = MockServer::start(vec![(200, "<article><p>That's the right answer!</p></article>")]);
+        let verdict = submit_answer(&client(&server, "abc123"), 2019, 5, Part::Two, &Answer::from(4)).unwrap();
+        let request = server.request();
+
+        assert_eq!(verdict, Verdict::Correct);
+        assert_eq!(request.request_line, "POST /2019/day/5/answer HTTP/1.1");
+        assert_eq!(request.header("Cookie"), Some("session=abc123"));
+        assert_eq!(request.body, "level=2&answer=4");
+    }
+
+    #[test]
+    fn unrecognised_response_should_produce_err_result() {
+        let server = MockServer::start(vec![(200, "<p>Puzzle inputs differ by user.  Please log in to get your puzzle input.</p>")]);
+        let err = submit_answer(&client(&server, "expired"), 2018, 5, Part::One, &Answer::from(10)).unwrap_err();
+        assert_eq!(err.to_string(), "Could not recognise the response to the submitted answer");
+    }
+
+    #[test]
+    fn grid_answer_is_not_sent() {
+        let server = MockServer::start(vec![]);
+        let grid = Answer::Grid(vec![String::from("#..#"), String::from("####")]);
+        let err = submit_answer(&client(&server, "abc123"), 2018, 10, Part::One, &grid).unwrap_err();
+        assert_eq!(err.to_string(), "A grid answer has to be read off and submitted by hand");
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::{Duration, Instant};
+
+/// Run `f`, returning its result and how long it took.
+///
+pub fn time<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// The spread of a set of timing samples.
+///
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarise the samples, or `None` if there are none.
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let median = match sorted.len() {
+            0 => return None,
+            n if n % 2 == 0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+            n => sorted[n / 2],
+        };
+        Some(Stats { min: sorted[0], median, max: sorted[sorted.len() - 1] })
+    }
+}
+
+/// A duration in the most readable unit, e.g. `1.234ms`.
+///
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.3}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.3}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.3}s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::*;
+
+    fn ms(m: u64) -> Duration {
+        Duration::from_millis(m)
+    }
+
+    #[test]
+    fn no_samples_have_no_stats() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn odd_number_of_samples() {
+        assert_eq!(Stats::from_samples(&[ms(5), ms(1), ms(3)]), Some(Stats { min: ms(1), median: ms(3), max: ms(5) }));
+    }
+
+    #[test]
+    fn even_number_of_samples_takes_mean_of_middle_two() {
+        assert_eq!(Stats::from_samples(&[ms(4), ms(1), ms(2), ms(9)]), Some(Stats { min: ms(1), median: ms(3), max: ms(9) }));
+    }
+}
+
+#[cfg(test)]
+mod format_duration_tests {
+    use super::*;
+
+    #[test]
+    fn picks_unit_by_magnitude() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.345µs");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.500ms");
+        assert_eq!(format_duration(Duration::from_millis(2_250)), "2.250s");
+    }
+}
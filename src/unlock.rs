@@ -0,0 +1,101 @@
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use std::cmp;
+use std::thread;
+use std::time::Duration;
+
+/// Puzzles unlock at midnight US Eastern, which in December is always UTC-5.
+///
+const UNLOCK_OFFSET_SECS: i32 = 5 * 3600;
+
+/// How much longer `wait_for_unlock` waits, to allow for our clock being ahead of the site's.
+///
+const UNLOCK_MARGIN: Duration = Duration::from_secs(2);
+
+/// When the day's puzzle unlocks.
+///
+pub fn unlock_time(year: u16, day: u8) -> DateTime<Utc> {
+    FixedOffset::west(UNLOCK_OFFSET_SECS)
+        .ymd(i32::from(year), 12, u32::from(day))
+        .and_hms(0, 0, 0)
+        .with_timezone(&Utc)
+}
+
+/// How long until the day's puzzle unlocks, or nothing if it already has.
+///
+pub fn time_until_unlock(year: u16, day: u8, now: DateTime<Utc>) -> Option<Duration> {
+    unlock_time(year, day).signed_duration_since(now).to_std().ok().filter(|d| *d > Duration::from_secs(0))
+}
+
+/// A countdown like `1d 02h 03m 04s`, leaving out the larger units while they are zero.
+///
+pub fn format_countdown(d: Duration) -> String {
+    let secs = d.as_secs() + if d.subsec_nanos() > 0 { 1 } else { 0 };
+    let (days, hours, minutes, seconds) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{}d {:02}h {:02}m {:02}s", days, hours, minutes, seconds)
+    } else if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Why the day's puzzle can not be fetched yet, if it is still locked.
+///
+pub fn check_unlocked(year: u16, day: u8, now: DateTime<Utc>) -> Result<(), String> {
+    match time_until_unlock(year, day, now) {
+        Some(remaining) => Err(format!("Day {} of {} unlocks in {}, at {} (use --wait to wait for it)",
+                                       day, year, format_countdown(remaining), unlock_time(year, day).format("%Y-%m-%d %H:%M UTC"))),
+        None => Ok(()),
+    }
+}
+
+/// Sleep until the day's puzzle unlocks, checking the clock at least every minute in case it
+/// jumps (e.g. the machine sleeps).
+///
+pub fn wait_for_unlock(year: u16, day: u8) {
+    if let Some(remaining) = time_until_unlock(year, day, Utc::now()) {
+        info!("Day {} of {} unlocks in {}, waiting", day, year, format_countdown(remaining));
+    } else {
+        return;
+    }
+    while let Some(remaining) = time_until_unlock(year, day, Utc::now()) {
+        trace!("{} to go", format_countdown(remaining));
+        thread::sleep(cmp::min(remaining, Duration::from_secs(60)));
+    }
+    thread::sleep(UNLOCK_MARGIN);
+}
+
+#[cfg(test)]
+mod unlock_tests {
+    use super::*;
+
+    #[test]
+    fn unlocks_at_midnight_eastern() {
+        assert_eq!(unlock_time(2018, 5), Utc.ymd(2018, 12, 5).and_hms(5, 0, 0));
+    }
+
+    #[test]
+    fn time_until_unlock_only_while_locked() {
+        assert_eq!(time_until_unlock(2018, 5, Utc.ymd(2018, 12, 4).and_hms(23, 30, 0)), Some(Duration::from_secs(5 * 3600 + 1800)));
+        assert_eq!(time_until_unlock(2018, 5, Utc.ymd(2018, 12, 5).and_hms(5, 0, 0)), None);
+        assert_eq!(time_until_unlock(2018, 5, Utc.ymd(2019, 1, 1).and_hms(0, 0, 0)), None);
+    }
+
+    #[test]
+    fn countdowns() {
+        assert_eq!(format_countdown(Duration::from_secs(93784)), "1d 02h 03m 04s");
+        assert_eq!(format_countdown(Duration::from_secs(7385)), "2h 03m 05s");
+        assert_eq!(format_countdown(Duration::from_secs(185)), "3m 05s");
+        assert_eq!(format_countdown(Duration::from_millis(4200)), "5s");
+    }
+
+    #[test]
+    fn premature_fetch_is_refused_with_countdown() {
+        assert_eq!(check_unlocked(2018, 5, Utc.ymd(2018, 12, 5).and_hms(4, 58, 30)),
+                   Err(String::from("Day 5 of 2018 unlocks in 1m 30s, at 2018-12-05 05:00 UTC (use --wait to wait for it)")));
+        assert_eq!(check_unlocked(2018, 5, Utc.ymd(2018, 12, 5).and_hms(5, 0, 1)), Ok(()));
+    }
+}
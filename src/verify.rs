@@ -0,0 +1,135 @@
+use cache::InputCache;
+use ledger::Ledger;
+use solver::{Answer, Part, SolveError, Solver};
+
+/// How a day's part fared against its recorded solution.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Pass,
+    Mismatch { expected: Answer, actual: Answer },
+    Failed(SolveError),
+    /// There is no cached input to run against.
+    NoInput,
+    /// The part ran, but has no recorded solution to compare with.
+    Unrecorded(Answer),
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        match self {
+            Outcome::Mismatch { .. } | Outcome::Failed(_) => true,
+            Outcome::Pass | Outcome::NoInput | Outcome::Unrecorded(_) => false,
+        }
+    }
+}
+
+/// The outcome of verifying one part of one day.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+/// Run both parts of every solver against its cached input and compare with the ledger's solutions.
+///
+pub fn verify_all(solvers: &[Box<dyn Solver>], cache: &InputCache, ledger: &Ledger) -> Vec<Check> {
+    let mut checks = Vec::new();
+    for solver in solvers {
+        let input = cache.get(solver.day());
+        for part in &[Part::One, Part::Two] {
+            let outcome = match input {
+                None => Outcome::NoInput,
+                Some(ref input) => verify_part(solver.as_ref(), *part, input, ledger),
+            };
+            checks.push(Check { day: solver.day(), part: *part, outcome });
+        }
+    }
+    checks
+}
+
+fn verify_part(solver: &dyn Solver, part: Part, input: &str, ledger: &Ledger) -> Outcome {
+    match (solver.solve(part, input), ledger.solution(solver.day(), part)) {
+        (Err(e), _) => Outcome::Failed(e),
+        (Ok(actual), None) => Outcome::Unrecorded(actual),
+        (Ok(ref actual), Some(expected)) if actual == expected => Outcome::Pass,
+        (Ok(actual), Some(expected)) => Outcome::Mismatch { expected: expected.clone(), actual },
+    }
+}
+
+/// A table of the checks, one row per day and part.
+///
+pub fn render_table(checks: &[Check]) -> String {
+    let mut table = format!("{:>3}  {:>4}  {:<6}  {}\n", "Day", "Part", "Result", "Detail");
+    for check in checks {
+        let (result, detail) = match check.outcome {
+            Outcome::Pass => ("PASS", String::new()),
+            Outcome::Mismatch { ref expected, ref actual } => ("FAIL", format!("expected '{}', got '{}'", expected, actual)),
+            Outcome::Failed(ref e) => ("FAIL", e.to_string()),
+            Outcome::NoInput => ("SKIP", String::from("no cached input")),
+            Outcome::Unrecorded(ref actual) => ("SKIP", format!("no recorded solution, got '{}'", actual)),
+        };
+        table.push_str(format!("{:>3}  {:>4}  {:<6}  {}", check.day, check.part, result, detail).trim_end());
+        table.push('\n');
+    }
+    let failures = checks.iter().filter(|c| c.outcome.is_failure()).count();
+    table.push_str(&format!("{} checked, {} passed, {} failed\n",
+                            checks.len(), checks.iter().filter(|c| c.outcome == Outcome::Pass).count(), failures));
+    table
+}
+
+#[cfg(test)]
+mod verify_tests {
+    use super::*;
+    use submit::Verdict;
+    use temp_dir::TempDir;
+    use day05::Day05;
+
+    fn temp_store(name: &str) -> (TempDir, InputCache, Ledger) {
+        let dir = TempDir::new(&format!("verify-{}", name));
+        let cache = InputCache::new(dir.path(), 2018);
+        let ledger = Ledger::open(cache.ledger_path()).unwrap();
+        (dir, cache, ledger)
+    }
+
+    #[test]
+    fn day_without_input_is_skipped() {
+        let (_dir, cache, ledger) = temp_store("no-input");
+        let checks = verify_all(&[Box::new(Day05)], &cache, &ledger);
+        assert_eq!(checks, vec![
+            Check { day: 5, part: Part::One, outcome: Outcome::NoInput },
+            Check { day: 5, part: Part::Two, outcome: Outcome::NoInput },
+        ]);
+    }
+
+    #[test]
+    fn answers_are_compared_with_recorded_solutions() {
+        let (_dir, cache, mut ledger) = temp_store("compare");
+        cache.put(5, "dabAcCaCBAcCcaDA\n").unwrap();
+        ledger.record(5, Part::One, &Answer::from(10), &Verdict::Correct).unwrap();
+        ledger.record(5, Part::Two, &Answer::from(5), &Verdict::Correct).unwrap();
+
+        let checks = verify_all(&[Box::new(Day05)], &cache, &ledger);
+        assert_eq!(checks[0].outcome, Outcome::Pass);
+        assert_eq!(checks[1].outcome, Outcome::Mismatch { expected: Answer::from(5), actual: Answer::from(4) });
+        assert!(checks[1].outcome.is_failure());
+    }
+
+    #[test]
+    fn table_has_a_row_per_check_and_a_summary() {
+        let checks = vec![
+            Check { day: 5, part: Part::One, outcome: Outcome::Pass },
+            Check { day: 5, part: Part::Two, outcome: Outcome::Mismatch { expected: Answer::from(5), actual: Answer::from(4) } },
+            Check { day: 6, part: Part::One, outcome: Outcome::NoInput },
+        ];
+        assert_eq!(render_table(&checks), "\
+Day  Part  Result  Detail
+  5     1  PASS
+  5     2  FAIL    expected '5', got '4'
+  6     1  SKIP    no cached input
+3 checked, 1 passed, 1 failed
+");
+    }
+}
@@ -0,0 +1,28 @@
+extern crate advent2018;
+
+use std::path::Path;
+
+use advent2018::examples::{self, DEFAULT_FIXTURES_DIR};
+use advent2018::registry;
+use advent2018::verify::Outcome;
+
+/// Days scaffolded by `new` whose examples have not been saved yet, as (year, day). `new` adds
+/// them here; take a day off once its fixtures are saved.
+const AWAITING_FIXTURES: &[(u16, u8)] = &[
+];
+
+#[test]
+fn checked_in_fixtures_pass() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_FIXTURES_DIR);
+    for solver in registry::all(2018) {
+        let fixtures = examples::load(&examples::fixture_path(&root, 2018, solver.day())).unwrap();
+        if AWAITING_FIXTURES.contains(&(2018, solver.day())) {
+            assert!(fixtures.is_empty(), "Day {} has fixtures now, so should come off AWAITING_FIXTURES", solver.day());
+        } else {
+            assert!(!fixtures.is_empty(), "No fixtures for day {}", solver.day());
+        }
+        for run in examples::run_examples(solver.as_ref(), &fixtures) {
+            assert_eq!((run.day, run.part, run.number, run.outcome), (run.day, run.part, run.number, Outcome::Pass));
+        }
+    }
+}
@@ -0,0 +1,36 @@
+extern crate advent2018;
+
+use std::time::Duration;
+
+use advent2018::cache::InputCache;
+use advent2018::fetch;
+use advent2018::http::Client;
+use temp_dir::TempDir;
+
+// the library's test helper, which is only built for its own unit tests
+#[path = "../src/temp_dir.rs"]
+#[allow(dead_code)]
+mod temp_dir;
+
+fn offline_client() -> Client {
+    // nothing listens here, so any request fails rather than reaching the site
+    Client::new("http://127.0.0.1:9", None, Duration::from_millis(100)).unwrap().with_retries(0, Duration::from_millis(1))
+}
+
+#[test]
+fn cached_input_needs_no_session_or_network() {
+    let dir = TempDir::new("it-fetch");
+    let cache = InputCache::new(dir.path(), 2018);
+    cache.put(5, "dabAcCaCBAcCcaDA\n").unwrap();
+
+    assert_eq!(fetch::input_for_day(&offline_client(), 5, &cache, false).unwrap(), "dabAcCaCBAcCcaDA\n");
+}
+
+#[test]
+fn uncached_input_needs_a_session() {
+    let dir = TempDir::new("it-fetch-empty");
+    let cache = InputCache::new(dir.path(), 2018);
+
+    let err = fetch::input_for_day(&offline_client(), 5, &cache, false).unwrap_err();
+    assert_eq!(err.to_string(), "No session found in ADV_SESS or the profile, a session is needed to download the input for day 5");
+}
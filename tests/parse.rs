@@ -0,0 +1,38 @@
+#[macro_use(regex_record)]
+extern crate advent2018;
+
+use advent2018::parse::{self, ParseError, ParseErrorKind};
+
+regex_record! {
+    #[derive(PartialEq, Eq, Debug)]
+    struct Point {
+        x: i32,
+        y: i32,
+        label: String,
+    }
+    from r"^(?P<x>-?\d+), (?P<y>-?\d+) (?P<label>\w+)$" expecting "a point like '1, -2 origin'"
+}
+
+#[test]
+fn regex_record_outside_the_crate() {
+    assert_eq!(parse::lines::<Point>("1, -2 a\n30, 4 b\n"), Ok(vec![
+        Point { x: 1, y: -2, label: String::from("a") },
+        Point { x: 30, y: 4, label: String::from("b") },
+    ]));
+}
+
+#[test]
+fn every_bad_line_is_reported() {
+    let errors = parse::lines::<Point>("1, 2 a\n1 2 b\nx, 2 c\n").unwrap_err();
+    assert_eq!(errors.iter().map(|e| e.line).collect::<Vec<Option<usize>>>(), vec![Some(2), Some(3)]);
+    assert_eq!(errors[0], ParseError::new(ParseErrorKind::MalformedLine("a point like '1, -2 origin'"), "1 2 b").at_line(2).at_column(1));
+}
+
+#[test]
+fn blocks_of_integers() {
+    let sums: Vec<i64> = parse::blocks("1 2\n3\n\n-4, 5\n")
+        .iter()
+        .map(|block| parse::signed_ints::<i64>(block).unwrap().iter().sum())
+        .collect();
+    assert_eq!(sums, vec![6, 1]);
+}